@@ -0,0 +1,29 @@
+use approx_entropy::{Bootstrap, SamplingMethod};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::SeedableRng;
+use rand_pcg::Pcg32;
+
+fn naive_entropies(c: &mut Criterion) {
+    let unnorm_distr: Vec<usize> = (1..=200).collect();
+    let mut bootstrap = Bootstrap::new(&unnorm_distr, 4, 2, Pcg32::seed_from_u64(1)).unwrap();
+
+    c.bench_function("naive_entropies", |b| {
+        b.iter(|| black_box(bootstrap.naive_entropies()))
+    });
+}
+
+#[cfg(feature = "rayon")]
+fn naive_entropies_par(c: &mut Criterion) {
+    let unnorm_distr: Vec<usize> = (1..=200).collect();
+    let mut bootstrap = Bootstrap::new(&unnorm_distr, 4, 2, Pcg32::seed_from_u64(1)).unwrap();
+
+    c.bench_function("naive_entropies_par", |b| {
+        b.iter(|| black_box(bootstrap.naive_entropies_par()))
+    });
+}
+
+#[cfg(not(feature = "rayon"))]
+criterion_group!(benches, naive_entropies);
+#[cfg(feature = "rayon")]
+criterion_group!(benches, naive_entropies, naive_entropies_par);
+criterion_main!(benches);
@@ -0,0 +1,195 @@
+#[cfg(feature = "ndarray")]
+use ndarray::ArrayView2;
+use rand_pcg::Pcg32;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    estimator::{DEFAULT_DEGREE, DEFAULT_NUM_GROUPS},
+    Bootstrap, Estimator, FittingError,
+};
+
+/// Configuration of the [`Bootstrap`] used for each distribution by [`estimate_batch`].
+///
+/// [`Bootstrap`]: struct.Bootstrap.html
+/// [`estimate_batch`]: fn.estimate_batch.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BootstrapConfig {
+    /// Number of groups of subsamples.
+    pub num_groups: usize,
+    /// Degree of the polynomial fitted to naive entropies.
+    pub degree: usize,
+    /// Base seed, from which the random number generator of each distribution is derived.
+    pub seed: u64,
+}
+
+impl BootstrapConfig {
+    pub fn new(num_groups: usize, degree: usize, seed: u64) -> Self {
+        BootstrapConfig {
+            num_groups,
+            degree,
+            seed,
+        }
+    }
+}
+
+impl Default for BootstrapConfig {
+    /// Same tunable parameters as `Estimator::from`, with seed zero.
+    fn default() -> Self {
+        BootstrapConfig::new(DEFAULT_NUM_GROUPS, DEFAULT_DEGREE, 0)
+    }
+}
+
+/// Estimates the entropy of each unnormalized distribution in `distrs`.
+///
+/// Each distribution is extrapolated by a [`Bootstrap`] configured by `config`.
+/// The random number generator of the `i`-th distribution is `Pcg32::new(config.seed, i)`,
+/// so the result is determined by the seed, and does not depend on
+/// how distributions are scheduled.
+/// With the `rayon` feature, distributions are processed in parallel
+/// in the global thread pool of rayon.
+///
+/// # Errors
+///
+/// Each distribution gives its own result: distributions with too few samples
+/// for the configuration (or an invalid configuration) give [`FittingError::Construction`].
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::{estimate_batch, BootstrapConfig};
+/// let distrs = vec![vec![1, 2, 3, 4, 5, 6], vec![10, 1], vec![1, 1]];
+/// let estimations = estimate_batch(&distrs, BootstrapConfig::new(3, 2, 1));
+/// assert!(estimations[0].is_ok());
+/// assert!(estimations[2].is_err());
+/// ```
+///
+/// [`Bootstrap`]: struct.Bootstrap.html
+/// [`FittingError::Construction`]: enum.FittingError.html#variant.Construction
+pub fn estimate_batch(
+    distrs: &[Vec<usize>],
+    config: BootstrapConfig,
+) -> Vec<Result<f64, FittingError>> {
+    let estimate = |(index, unnorm_distr): (usize, &Vec<usize>)| {
+        let rng = item_rng(config.seed, index);
+        let bootstrap = Bootstrap::new(unnorm_distr, config.num_groups, config.degree, rng)?;
+        Estimator::new(bootstrap).entropy()
+    };
+
+    #[cfg(feature = "rayon")]
+    let estimations = distrs.par_iter().enumerate().map(estimate).collect();
+    #[cfg(not(feature = "rayon"))]
+    let estimations = distrs.iter().enumerate().map(estimate).collect();
+    estimations
+}
+
+/// Estimates the entropy of each row of `matrix`,
+/// each row being an independent unnormalized distribution.
+///
+/// Each row is extrapolated by a [`Bootstrap`] with `num_groups` groups and degree `degree`.
+/// The random number generator of the `i`-th row is `Pcg32::new(seed, i)`,
+/// so rows are independent of each other and the whole result is determined by `seed`.
+///
+/// # Errors
+///
+/// Each row gives its own result: rows with too few samples for the configuration
+/// (or an invalid configuration) give [`FittingError::Construction`],
+/// while the other rows are estimated as usual.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::estimate_rows;
+/// let matrix = ndarray::arr2(&[[5, 5, 5, 5], [17, 1, 1, 1], [1, 0, 0, 0]]);
+/// let estimations = estimate_rows(matrix.view(), 1, 3, 2);
+/// assert!(estimations[0].is_ok());
+/// assert!(estimations[2].is_err());
+/// ```
+///
+/// [`Bootstrap`]: struct.Bootstrap.html
+/// [`FittingError::Construction`]: enum.FittingError.html#variant.Construction
+#[cfg(feature = "ndarray")]
+pub fn estimate_rows(
+    matrix: ArrayView2<usize>,
+    seed: u64,
+    num_groups: usize,
+    degree: usize,
+) -> Vec<Result<f64, FittingError>> {
+    matrix
+        .outer_iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let rng = item_rng(seed, index);
+            let bootstrap = Bootstrap::from_ndarray(row, num_groups, degree, rng)?;
+            Estimator::new(bootstrap).entropy()
+        })
+        .collect()
+}
+
+/// Random number generator of the `index`-th item of a batch.
+fn item_rng(seed: u64, index: usize) -> Pcg32 {
+    Pcg32::new(seed, index as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ndarray")]
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn batch_matches_individual() {
+        let distrs: Vec<Vec<usize>> = (1..20).map(|i| (1..=i).collect()).collect();
+        let config = BootstrapConfig::new(3, 2, 5);
+        let estimations = estimate_batch(&distrs, config);
+
+        assert_eq!(estimations.len(), distrs.len());
+        for (index, (estimation, unnorm_distr)) in estimations.into_iter().zip(&distrs).enumerate()
+        {
+            let expected = Bootstrap::new(unnorm_distr, 3, 2, Pcg32::new(5, index as u64))
+                .map(|bootstrap| Estimator::new(bootstrap).entropy().unwrap());
+            match expected {
+                Ok(expected) => assert_eq!(estimation.unwrap(), expected),
+                Err(_) => assert!(matches!(estimation, Err(FittingError::Construction(_)))),
+            }
+        }
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn uniform_rows() {
+        let matrix = ndarray::arr2(&[
+            [50; 8],
+            [100, 100, 100, 100, 0, 0, 0, 0],
+            [200, 200, 0, 0, 0, 0, 0, 0],
+        ]);
+        let estimations = estimate_rows(matrix.view(), 1, 3, 2);
+
+        assert_eq!(estimations.len(), 3);
+        for (estimation, support) in estimations.into_iter().zip([8., 4., 2.]) {
+            assert_float_eq!(estimation.unwrap(), f64::ln(support), abs <= 0.05);
+        }
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn too_small_row() {
+        let matrix = ndarray::arr2(&[[10, 10], [1, 2]]);
+        let estimations = estimate_rows(matrix.view(), 1, 3, 2);
+
+        assert!(estimations[0].is_ok());
+        assert!(matches!(estimations[1], Err(FittingError::Construction(_))));
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn reproducible() {
+        let matrix = ndarray::arr2(&[[3, 5, 7], [2, 4, 8]]);
+        let first = estimate_rows(matrix.view(), 7, 3, 2);
+        let second = estimate_rows(matrix.view(), 7, 3, 2);
+
+        for (first, second) in first.into_iter().zip(second) {
+            assert_eq!(first.unwrap(), second.unwrap());
+        }
+    }
+}
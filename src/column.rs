@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+
+use crate::{utils::count_dup, NaiveEstimator};
+
+/// Naive entropy of a column of categorical codes, ignoring null entries.
+///
+/// This matches the representation of categorical columns in columnar formats
+/// (like arrow): each value is the code of a category and
+/// an optional mask marks null entries with `true`.
+///
+/// # Remarks
+///
+/// An empty or all-null column has zero entropy.
+///
+/// # Panics
+///
+/// If `null_mask` is given and its length differs from the length of `values`.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::entropy_from_column;
+/// let values = [0, 1, 7, 1];
+/// let null_mask = [false, false, true, false];
+/// let entropy = entropy_from_column(&values, Some(&null_mask));
+/// // Naive entropy of [1, 2]
+/// # assert!((entropy - 0.6365142).abs() < 1e-6);
+/// ```
+pub fn entropy_from_column(values: &[u32], null_mask: Option<&[bool]>) -> f64 {
+    let non_null: Vec<u32> = match null_mask {
+        Some(null_mask) => {
+            assert_eq!(
+                values.len(),
+                null_mask.len(),
+                "values and null mask must have the same length"
+            );
+            values
+                .iter()
+                .zip(null_mask)
+                .filter(|(_, &is_null)| !is_null)
+                .map(|(&value, _)| value)
+                .collect()
+        }
+        None => values.to_vec(),
+    };
+
+    let unnorm_distr = count_dup(&non_null);
+    match NaiveEstimator::new(&unnorm_distr) {
+        Ok(naive_estimator) => naive_estimator.entropy(),
+        Err(_) => 0.,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+    use test_case::test_case;
+
+    #[test_case(&[], None, 0.; "empty")]
+    #[test_case(&[3, 4], Some(&[true, true]), 0.; "all_null")]
+    #[test_case(&[0, 1, 0, 1], None, 2_f64.ln(); "no_mask")]
+    #[test_case(&[0, 1, 2, 2, 3], Some(&[false, false, true, true, true]), 2_f64.ln(); "nulls_excluded")]
+    fn column_entropy(values: &[u32], null_mask: Option<&[bool]>, expected: f64) {
+        assert_float_eq!(
+            entropy_from_column(values, null_mask),
+            expected,
+            abs <= 1e-12
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn mask_length_mismatch() {
+        entropy_from_column(&[0, 1], Some(&[false]));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    utils::{checked_total, digamma},
+    TooManySamples,
+};
+
+const MIN_LN_CONCENTRATION: f64 = -10.;
+const MAX_LN_CONCENTRATION: f64 = 10.;
+const OPTIMIZATION_STEPS: usize = 100;
+
+/// Entropy estimation under a Dirichlet-multinomial model,
+/// with the concentration parameter inferred from the data.
+///
+/// The concentration `α` of a symmetric Dirichlet prior over `support` symbols
+/// is fitted by maximizing the marginal likelihood of `unnorm_distr`,
+/// and the posterior-mean entropy under that `α` is returned.
+/// A large `α` shrinks the estimate towards the uniform distribution,
+/// while a small `α` leaves it close to the naive (plug-in) estimate.
+///
+/// # Remarks
+///
+/// Symbols never observed must still be counted in `support`.
+/// If `support` is smaller than the length of `unnorm_distr`,
+/// the length of `unnorm_distr` is used instead.
+///
+/// The optimization is a golden-section search over `ln α`
+/// in the bracket `[-10, 10]`.
+///
+/// # Errors
+///
+/// If the total number of samples does not fit in a `usize`.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::dirichlet_multinomial_entropy;
+/// let unnorm_distr = [3, 1, 4, 1, 5];
+/// let support = 8;
+/// let entropy = dirichlet_multinomial_entropy(&unnorm_distr, support).unwrap();
+/// println!("Entropy estimation: {:?}", entropy);
+/// ```
+pub fn dirichlet_multinomial_entropy(
+    unnorm_distr: &[usize],
+    support: usize,
+) -> Result<f64, TooManySamples> {
+    let all = checked_total(unnorm_distr).ok_or(TooManySamples)?;
+    let support = support.max(unnorm_distr.len());
+    if support == 0 {
+        return Ok(0.);
+    }
+
+    let concentration = max_likelihood_concentration(unnorm_distr, all, support);
+    Ok(posterior_mean_entropy(
+        unnorm_distr,
+        all,
+        support,
+        concentration,
+    ))
+}
+
+/// Logarithm of the Dirichlet-multinomial marginal likelihood, up to a constant.
+///
+/// Uses `ln Γ(n + a) - ln Γ(a) = Σ_{j < n} ln(a + j)` to avoid the gamma function.
+fn ln_likelihood(unnorm_distr: &[usize], all: usize, support: usize, concentration: f64) -> f64 {
+    let total_concentration = support as f64 * concentration;
+
+    let numerator: f64 = unnorm_distr
+        .iter()
+        .flat_map(|&count| (0..count).map(move |j| (concentration + j as f64).ln()))
+        .sum();
+    let denominator: f64 = (0..all)
+        .map(|j| (total_concentration + j as f64).ln())
+        .sum();
+    numerator - denominator
+}
+
+/// Golden-section search of the concentration maximizing the likelihood.
+fn max_likelihood_concentration(unnorm_distr: &[usize], all: usize, support: usize) -> f64 {
+    let inv_phi = (5_f64.sqrt() - 1.) / 2.;
+    let objective =
+        |ln_concentration: f64| ln_likelihood(unnorm_distr, all, support, ln_concentration.exp());
+
+    let (mut lower, mut upper) = (MIN_LN_CONCENTRATION, MAX_LN_CONCENTRATION);
+    let mut left = upper - inv_phi * (upper - lower);
+    let mut right = lower + inv_phi * (upper - lower);
+    let (mut left_value, mut right_value) = (objective(left), objective(right));
+    for _ in 0..OPTIMIZATION_STEPS {
+        if left_value < right_value {
+            lower = left;
+            left = right;
+            left_value = right_value;
+            right = lower + inv_phi * (upper - lower);
+            right_value = objective(right);
+        } else {
+            upper = right;
+            right = left;
+            right_value = left_value;
+            left = upper - inv_phi * (upper - lower);
+            left_value = objective(left);
+        }
+    }
+    ((lower + upper) / 2.).exp()
+}
+
+/// Posterior mean of the entropy under a symmetric Dirichlet prior.
+///
+/// With posterior parameters `a_i = n_i + α` and `A = Σ a_i`, it is
+/// `ψ(A + 1) - Σ (a_i / A) ψ(a_i + 1)`.
+fn posterior_mean_entropy(
+    unnorm_distr: &[usize],
+    all: usize,
+    support: usize,
+    concentration: f64,
+) -> f64 {
+    let total = all as f64 + support as f64 * concentration;
+    let unseen = (support - unnorm_distr.len()) as f64;
+
+    let seen_term: f64 = unnorm_distr
+        .iter()
+        .map(|&count| {
+            let a = count as f64 + concentration;
+            a * digamma(a + 1.)
+        })
+        .sum();
+    let unseen_term = unseen * concentration * digamma(concentration + 1.);
+
+    digamma(total + 1.) - (seen_term + unseen_term) / total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+    use test_case::test_case;
+
+    use crate::NaiveEstimator;
+
+    #[test_case(&[10; 8], 8; "uniform_eight")]
+    #[test_case(&[25, 24, 26, 25], 4; "almost_uniform_four")]
+    fn near_uniform(unnorm_distr: &[usize], support: usize) {
+        let estimation = dirichlet_multinomial_entropy(unnorm_distr, support).unwrap();
+        assert_float_eq!(estimation, (support as f64).ln(), abs <= 0.05);
+    }
+
+    #[test]
+    fn concentrated() {
+        let unnorm_distr = [60, 1, 1];
+        let support = 8;
+        let estimation = dirichlet_multinomial_entropy(&unnorm_distr, support).unwrap();
+        let naive = NaiveEstimator::new(&unnorm_distr).unwrap().entropy();
+
+        assert!(estimation < (support as f64).ln() / 2.);
+        assert_float_eq!(estimation, naive, abs <= 0.2);
+    }
+
+    #[test]
+    fn empty() {
+        assert_float_eq!(
+            dirichlet_multinomial_entropy(&[], 0).unwrap(),
+            0.,
+            abs <= 1e-12
+        );
+    }
+
+    #[test]
+    fn too_many_samples() {
+        let result = dirichlet_multinomial_entropy(&[usize::MAX, 1], 2);
+        assert!(matches!(result, Err(TooManySamples)));
+    }
+}
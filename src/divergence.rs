@@ -0,0 +1,133 @@
+use core::hash::Hash;
+use rand::rngs::ThreadRng;
+
+use crate::{estimator::FittingError, utils::count_dup_map, Bootstrap, Estimator};
+
+/// Kullback-Leibler divergence between the distributions of two samples.
+///
+/// The divergence is computed as `D(P || Q) = H(P, Q) - H(P)`,
+/// where the entropy `H(P)` is estimated with an [`Estimator`]
+/// and the cross entropy `H(P, Q) = - Σ p_i ln q_i` is the naive one,
+/// over the union of the outcomes observed in both samples.
+///
+/// # Remarks
+///
+/// If `P` has an outcome never observed in `Q`, the divergence is infinite.
+///
+/// Default values are given to tunable parameters, as in `Estimator::from`.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::KlEstimator;
+/// let p: Vec<usize> = (0..100).map(|i| i % 4).collect();
+/// let q: Vec<usize> = (0..100).map(|i| i % 3 % 4 + i % 2).collect();
+/// let mut estimator = KlEstimator::from_samples(&p, &q);
+/// println!("Divergence: {:?}", estimator.kl_divergence()); // Random result
+/// ```
+///
+/// [`Estimator`]: struct.Estimator.html
+#[derive(Debug)]
+pub struct KlEstimator {
+    p: Estimator<Bootstrap<ThreadRng>>,
+    p_unnorm_distr: Vec<usize>,
+    q_unnorm_distr: Vec<usize>,
+}
+
+impl KlEstimator {
+    /// Constructs a new `KlEstimator` from samples of `P` and samples of `Q`.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples of `P` for the default number of groups.
+    pub fn from_samples<T>(p: &[T], q: &[T]) -> Self
+    where
+        T: Hash + Eq + Clone,
+    {
+        let p_counts = count_dup_map(p);
+        let q_counts = count_dup_map(q);
+
+        let (p_unnorm_distr, q_unnorm_distr) = p_counts
+            .iter()
+            .map(|(outcome, &count)| (count, q_counts.get(outcome).copied().unwrap_or(0)))
+            .chain(
+                q_counts
+                    .iter()
+                    .filter(|(outcome, _)| !p_counts.contains_key(outcome))
+                    .map(|(_, &count)| (0, count)),
+            )
+            .unzip();
+
+        KlEstimator {
+            p: Estimator::from(p),
+            p_unnorm_distr,
+            q_unnorm_distr,
+        }
+    }
+
+    /// Estimates the Kullback-Leibler divergence `D(P || Q)`.
+    ///
+    /// Returns `f64::INFINITY` if `P` has an outcome never observed in `Q`.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities in the estimation of `H(P)`.
+    pub fn kl_divergence(&mut self) -> Result<f64, FittingError> {
+        if self
+            .p_unnorm_distr
+            .iter()
+            .zip(&self.q_unnorm_distr)
+            .any(|(&p_count, &q_count)| p_count > 0 && q_count == 0)
+        {
+            return Ok(f64::INFINITY);
+        }
+        Ok(self.cross_entropy() - self.p.entropy()?)
+    }
+
+    /// Naive cross entropy `H(P, Q) = - Σ p_i ln q_i`.
+    fn cross_entropy(&self) -> f64 {
+        let p_total: f64 = self.p_unnorm_distr.iter().map(|&count| count as f64).sum();
+        let q_total: f64 = self.q_unnorm_distr.iter().map(|&count| count as f64).sum();
+        self.p_unnorm_distr
+            .iter()
+            .zip(&self.q_unnorm_distr)
+            .filter(|(&p_count, _)| p_count > 0)
+            .map(|(&p_count, &q_count)| {
+                -(p_count as f64 / p_total) * (q_count as f64 / q_total).ln()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn identical() {
+        let samples: Vec<usize> = (0..400).map(|i| i % 4).collect();
+        let mut estimator = KlEstimator::from_samples(&samples, &samples);
+
+        assert_float_eq!(estimator.kl_divergence().unwrap(), 0., abs <= 0.1);
+    }
+
+    #[test]
+    fn disjoint() {
+        let p: Vec<usize> = (0..100).map(|i| i % 4).collect();
+        let q: Vec<usize> = (0..100).map(|i| 4 + i % 4).collect();
+        let mut estimator = KlEstimator::from_samples(&p, &q);
+
+        assert_eq!(estimator.kl_divergence().unwrap(), f64::INFINITY);
+    }
+
+    #[test]
+    fn cross_entropy() {
+        let p = ['a', 'a', 'b', 'b', 'a', 'b', 'a', 'b'];
+        let q = ['a', 'b', 'b', 'b', 'c', 'c', 'c', 'c'];
+        let estimator = KlEstimator::from_samples(&p, &q);
+        let expected = -0.5 * (1_f64 / 8.).ln() - 0.5 * (3_f64 / 8.).ln();
+
+        assert_float_eq!(estimator.cross_entropy(), expected, abs <= 1e-12);
+    }
+}
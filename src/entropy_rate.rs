@@ -0,0 +1,200 @@
+use core::hash::Hash;
+use rand::SeedableRng;
+use rand_pcg::Pcg32;
+
+use crate::{
+    estimator::{FittingError, DEFAULT_DEGREE, DEFAULT_NUM_GROUPS},
+    utils::count_dup_ordered,
+    Bootstrap, ConditionalEntropyEstimator, Estimator,
+};
+
+/// Block entropy `H(X_1, ..., X_n)` of `sequence`, over all overlapping blocks (n-grams)
+/// of length `n`, extrapolated by an [`Estimator`] seeded with `seed`.
+///
+/// Blocks are counted as with [`count_dup`], in order of first appearance,
+/// so that the result is reproducible.
+/// Default values are given to tunable parameters, as in `Estimator::from_seed`.
+///
+/// See [`block_entropy_scaling`] for the naive block entropies of several lengths.
+///
+/// # Errors
+///
+/// If there are too few blocks for the default number of groups
+/// (in particular, if `sequence` is shorter than `n`),
+/// or if there are numerical instabilities.
+///
+/// # Panics
+///
+/// If `n` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::block_entropy;
+/// let sequence: Vec<usize> = (0..100).map(|i| (i * i) % 5).collect();
+/// println!("Block entropy: {:?}", block_entropy(&sequence, 2, 1));
+/// ```
+///
+/// [`Estimator`]: struct.Estimator.html
+/// [`count_dup`]: fn.count_dup.html
+/// [`block_entropy_scaling`]: fn.block_entropy_scaling.html
+pub fn block_entropy<T>(sequence: &[T], n: usize, seed: u64) -> Result<f64, FittingError>
+where
+    T: Hash + Eq,
+{
+    assert!(n > 0, "blocks must have at least one symbol");
+    let blocks: Vec<&[T]> = sequence.windows(n).collect();
+    let sampling_method = Bootstrap::new(
+        &count_dup_ordered(&blocks),
+        DEFAULT_NUM_GROUPS,
+        DEFAULT_DEGREE,
+        Pcg32::seed_from_u64(seed),
+    )?;
+    Estimator::new(sampling_method).entropy()
+}
+
+/// Entropy rate of a sequence, from extrapolated entropies.
+///
+/// The entropy rate is estimated as the conditional entropy of a symbol
+/// given the `order` previous ones, `H(X_t | X_{t - order}, ..., X_{t - 1})`,
+/// with a [`ConditionalEntropyEstimator`] over all overlapping blocks of `order + 1` symbols.
+/// This accounts for the dependence between consecutive symbols,
+/// which estimators assuming independent samples overlook, overestimating entropy.
+///
+/// # Remarks
+///
+/// With `order = 0`, there is no context and this is the marginal entropy of the symbols.
+/// Higher orders capture longer dependences, but need more samples:
+/// each of the `K^order` possible contexts must be observed several times.
+///
+/// Default values are given to tunable parameters, as in `Estimator::from_seed`.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::EntropyRateEstimator;
+/// let sequence: Vec<usize> = (0..200).map(|i| (i * i) % 5).collect();
+/// let mut estimator = EntropyRateEstimator::from_sequence(&sequence, 1);
+/// println!("Entropy rate: {:?}", estimator.entropy_rate()); // Random result
+/// ```
+///
+/// [`ConditionalEntropyEstimator`]: struct.ConditionalEntropyEstimator.html
+#[derive(Debug)]
+pub struct EntropyRateEstimator {
+    conditional: ConditionalEntropyEstimator,
+    order: usize,
+}
+
+impl EntropyRateEstimator {
+    /// Constructs a new `EntropyRateEstimator` from a sequence,
+    /// conditioning on the `order` previous symbols, with a random seed.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few blocks of `order + 1` symbols for the default number of groups.
+    pub fn from_sequence<T>(sequence: &[T], order: usize) -> Self
+    where
+        T: Hash + Eq + Clone,
+    {
+        Self::from_sequence_with_seed(sequence, order, rand::random())
+    }
+
+    /// Constructs a new `EntropyRateEstimator` from a sequence,
+    /// conditioning on the `order` previous symbols,
+    /// using random number generators seeded with `seed`.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few blocks of `order + 1` symbols for the default number of groups.
+    pub fn from_sequence_with_seed<T>(sequence: &[T], order: usize, seed: u64) -> Self
+    where
+        T: Hash + Eq + Clone,
+    {
+        let pairs: Vec<(&[T], &T)> = sequence
+            .windows(order + 1)
+            .map(|block| (&block[..order], &block[order]))
+            .collect();
+
+        EntropyRateEstimator {
+            conditional: ConditionalEntropyEstimator::from_pairs_with_seed(&pairs, seed),
+            order,
+        }
+    }
+
+    /// Returns the number of previous symbols conditioned on.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Estimates the entropy rate, as the conditional entropy
+    /// of a symbol given the `order` previous ones.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities in any of the two estimations.
+    pub fn entropy_rate(&mut self) -> Result<f64, FittingError> {
+        self.conditional.conditional_entropy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    use crate::{count_dup, ConstructionError};
+
+    #[test]
+    fn periodic() {
+        let sequence: Vec<usize> = (0..300).map(|i| i % 3).collect();
+
+        let mut marginal = EntropyRateEstimator::from_sequence_with_seed(&sequence, 0, 1);
+        assert_float_eq!(marginal.entropy_rate().unwrap(), 3_f64.ln(), abs <= 0.05);
+
+        let mut estimator = EntropyRateEstimator::from_sequence_with_seed(&sequence, 1, 1);
+        assert_eq!(estimator.order(), 1);
+        assert_float_eq!(estimator.entropy_rate().unwrap(), 0., abs <= 1e-9);
+    }
+
+    #[test]
+    fn block_entropy_single_symbols() {
+        let sequence: Vec<usize> = (0..60).map(|i| (i * i) % 7).collect();
+        let expected = Estimator::from_seed(&count_dup_ordered(&sequence), 3)
+            .entropy()
+            .unwrap();
+
+        assert_eq!(block_entropy(&sequence, 1, 3).unwrap(), expected);
+    }
+
+    #[test]
+    fn block_entropy_periodic() {
+        let sequence: Vec<usize> = (0..300).map(|i| i % 3).collect();
+        let single = block_entropy(&sequence, 1, 1).unwrap();
+        let pairs = block_entropy(&sequence, 2, 1).unwrap();
+
+        // Pairs are determined by their first symbol
+        assert_float_eq!(pairs, single, abs <= 0.05);
+    }
+
+    #[test]
+    fn block_entropy_too_short() {
+        let result = block_entropy(&[0, 1, 2], 4, 1);
+        assert!(matches!(
+            result,
+            Err(FittingError::Construction(
+                ConstructionError::TooFewSamples(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn order_zero_is_marginal() {
+        let sequence: Vec<usize> = (0..400).map(|i| (i * i) % 7).collect();
+        let mut estimator = EntropyRateEstimator::from_sequence_with_seed(&sequence, 0, 1);
+        let expected = Estimator::from_seed(&count_dup(&sequence), 1)
+            .entropy()
+            .unwrap();
+
+        assert_float_eq!(estimator.entropy_rate().unwrap(), expected, abs <= 0.05);
+    }
+}
@@ -1,18 +1,27 @@
-use core::hash::Hash;
-use nalgebra::{DMatrix, DVector};
-use rand::rngs::ThreadRng;
+use core::{fmt, hash::Hash, marker::PhantomData};
+use nalgebra::{storage::StorageMut, DMatrix, DVector, Dim, Matrix, RealField, U1};
+use rand::{rngs::ThreadRng, SeedableRng};
+use rand_pcg::Pcg32;
 use thiserror::Error;
 
-use crate::{Bootstrap, SamplingMethod};
+use crate::{
+    traits::num_distinct_sizes, Bootstrap, ConstructionError, LogBase, NaiveEstimator, Reseed,
+    SamplingMethod,
+};
 
 mod direct;
-mod naive;
+mod fixed_degree;
+#[cfg(feature = "csv")]
+mod from_csv;
 
-pub use direct::DirectEstimator;
-pub use naive::NaiveEstimator;
+pub use direct::{DirectEstimator, FitDiagnostics};
+pub use fixed_degree::FixedDegreeEstimator;
+#[cfg(feature = "csv")]
+pub use from_csv::ReadError;
 
-const DEFAULT_NUM_GROUPS: usize = 3;
-const DEFAULT_DEGREE: usize = 2;
+pub(crate) const DEFAULT_NUM_GROUPS: usize = 3;
+pub(crate) const DEFAULT_DEGREE: usize = 2;
+const MSE_REPLICATES: usize = 10;
 
 /// Entropy estimator
 ///
@@ -35,66 +44,939 @@ const DEFAULT_DEGREE: usize = 2;
 /// let mut estimator = Estimator::from(samples);
 /// println!("Entropy estimation: {:?}", estimator.entropy()); // Random result
 /// ```
+///
+/// Estimation in single precision.
+/// ```
+/// # use approx_entropy::Estimator;
+/// let unnorm_distr = [1, 2, 3, 4, 5, 6];
+/// let mut estimator = Estimator::from(unnorm_distr).with_float::<f32>();
+/// let entropy: f32 = estimator.entropy().unwrap(); // Random result
+/// ```
 #[derive(Debug, PartialEq)]
-pub struct Estimator<M> {
+pub struct Estimator<M, F = f64> {
     sampling_method: M,
+    float: PhantomData<F>,
+}
+
+/// Reason why fitting naive entropy estimations failed.
+#[derive(Error, Debug)]
+pub enum FittingError {
+    #[error("Failed to estimate entropy because of numerical instability: the least squares system is singular.")]
+    Singular,
+    #[error("Failed to estimate entropy because the polynomial fit failed: {0}")]
+    PolyFit(String),
+    #[error("Failed to estimate entropy because there are too few distinct subsample sizes for the degree.")]
+    DegeneratePoints,
+    #[error("Failed to estimate entropy because no candidate is valid.")]
+    NoCandidate,
+    #[error(transparent)]
+    Construction(#[from] ConstructionError),
+}
+
+/// Common interface of the estimators extrapolating naive entropy estimations,
+/// so that they can be used interchangeably.
+///
+/// # Examples
+///
+/// Estimators as trait objects.
+/// ```
+/// # use approx_entropy::{DirectEstimator, EntropyEstimate, Estimator};
+/// let unnorm_distr = [1, 2, 3, 4, 5, 6];
+/// let mut estimators: Vec<Box<dyn EntropyEstimate>> = vec![
+///     Box::new(Estimator::from(unnorm_distr)),
+///     Box::new(DirectEstimator::from(unnorm_distr)),
+/// ];
+/// for estimator in estimators.iter_mut() {
+///     println!("Entropy estimation: {:?}", estimator.entropy()); // Random result
+/// }
+/// ```
+pub trait EntropyEstimate {
+    /// Estimates the entropy of the underlying distribution.
+    ///
+    /// # Errors
+    ///
+    /// If the fit of naive entropy estimations fails.
+    fn entropy(&mut self) -> Result<f64, FittingError>;
 }
 
+/// Reason why changing a parameter of the sampling method of an [`Estimator`] failed.
+///
+/// [`Estimator`]: struct.Estimator.html
 #[derive(Error, Debug)]
-#[error("Failed to estimate entropy because of numerical instability.")]
-pub struct FittingError;
+pub enum ParameterError {
+    #[error("Failed to change the degree: {0}")]
+    Degree(String),
+    #[error("Failed to change the number of groups: {0}")]
+    NumGroups(String),
+}
+
+/// Result of fitting a polynomial in `1/n` to naive entropy estimations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntropyFit {
+    /// Entropy estimation, i.e. the constant term of the polynomial.
+    pub estimate: f64,
+    /// Coefficients of the polynomial in `1/n`, from the constant term upwards.
+    pub coefficients: Vec<f64>,
+    /// Naive entropy estimations used for the fit, as pairs `(size, value)`.
+    pub naive_entropies: Vec<(usize, f64)>,
+}
+
+impl EntropyFit {
+    /// Returns the entropy estimation, replaced by zero if it is negative.
+    ///
+    /// See [`is_clamped`] to know if the replacement happened.
+    ///
+    /// [`is_clamped`]: #method.is_clamped
+    pub fn clamped_estimate(&self) -> f64 {
+        self.estimate.max(0.)
+    }
+
+    /// Returns `true` if the entropy estimation is negative,
+    /// so that [`clamped_estimate`] replaces it by zero.
+    ///
+    /// A negative estimation means the extrapolation is not trustworthy.
+    ///
+    /// [`clamped_estimate`]: #method.clamped_estimate
+    pub fn is_clamped(&self) -> bool {
+        self.estimate < 0.
+    }
+}
 
 /// # Basic methods
 impl<M> Estimator<M>
 where
     M: SamplingMethod,
 {
-    /// Constructs a new `Estimator`.
-    ///
-    /// # Remarks
-    ///
-    /// The trait `From<M>` is also implemented for convenience.
-    pub fn new(sampling_method: M) -> Self {
-        Estimator { sampling_method }
+    /// Constructs a new `Estimator`.
+    ///
+    /// # Remarks
+    ///
+    /// The trait `From<M>` is also implemented for convenience.
+    pub fn new(sampling_method: M) -> Self {
+        Estimator {
+            sampling_method,
+            float: PhantomData,
+        }
+    }
+
+    /// Estimates the entropy and returns all the details of the fit.
+    ///
+    /// See [`EntropyFit`] for the details.
+    ///
+    /// # Remarks
+    ///
+    /// If a single element was observed, no subsample is drawn:
+    /// all coefficients are zero and there are no naive entropy estimations.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`entropy`].
+    ///
+    /// [`EntropyFit`]: struct.EntropyFit.html
+    /// [`entropy`]: #method.entropy
+    pub fn entropy_fit(&mut self) -> Result<EntropyFit, FittingError> {
+        let degree = self.sampling_method.degree();
+        if let Some(estimate) = check_fit(&self.sampling_method, degree)? {
+            return Ok(EntropyFit {
+                estimate,
+                coefficients: vec![0.; degree + 1],
+                naive_entropies: Vec::new(),
+            });
+        }
+        let naive_entropies = self.sampling_method.naive_entropies();
+        let coefficients = fit_polynomial(&naive_entropies, degree, |_| 1., 0.)?;
+        Ok(EntropyFit {
+            estimate: coefficients[0],
+            coefficients,
+            naive_entropies,
+        })
+    }
+
+    /// Estimates the entropy, replacing negative estimations by zero.
+    ///
+    /// The extrapolation of [`entropy`] can give negative values,
+    /// which are not valid entropies.
+    /// Use [`entropy_fit`] and [`EntropyFit::is_clamped`] to know if this happened.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`entropy`].
+    ///
+    /// [`entropy`]: #method.entropy
+    /// [`entropy_fit`]: #method.entropy_fit
+    /// [`EntropyFit::is_clamped`]: struct.EntropyFit.html#method.is_clamped
+    pub fn entropy_clamped(&mut self) -> Result<f64, FittingError> {
+        Ok(self.entropy_fit()?.clamped_estimate())
+    }
+
+    /// Estimates the entropy by weighted least squares,
+    /// where each naive entropy estimation of a subsample of size `n` has weight `w(n)`.
+    ///
+    /// Naive entropy estimations from large subsamples are more reliable,
+    /// so increasing weights make them dominate the fit.
+    /// For example, `|n| n as f64` scales each row of the least squares problem by `sqrt(n)`.
+    /// Constant weights give the same result as [`entropy`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`entropy`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::Estimator;
+    /// let unnorm_distr = [1, 2, 3, 4, 5, 6];
+    /// let mut estimator = Estimator::from(unnorm_distr);
+    /// println!("Entropy estimation: {:?}", estimator.entropy_weighted(|n| n as f64)); // Random result
+    /// ```
+    ///
+    /// [`entropy`]: #method.entropy
+    pub fn entropy_weighted<W>(&mut self, w: W) -> Result<f64, FittingError>
+    where
+        W: Fn(usize) -> f64,
+    {
+        if let Some(entropy) = check_fit(&self.sampling_method, self.sampling_method.degree())? {
+            return Ok(entropy);
+        }
+        let naive_entropies = self.sampling_method.naive_entropies();
+        let coefficients = fit_polynomial(&naive_entropies, self.sampling_method.degree(), w, 0.)?;
+        Ok(coefficients[0])
+    }
+
+    /// Estimates the entropy with a ridge penalty on the polynomial fit.
+    ///
+    /// The squared error of the fit is increased by `lambda Σ_{k >= 1} c_k^2`,
+    /// damping all coefficients but the constant term, which is the estimation.
+    /// A small `lambda` barely changes the result of [`entropy`],
+    /// while a large `lambda` pulls it towards the average of the naive entropy estimations
+    /// (weighted by the square of the subsample size).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`entropy`].
+    ///
+    /// # Panics
+    ///
+    /// If `lambda` is negative or NaN.
+    ///
+    /// [`entropy`]: #method.entropy
+    pub fn entropy_ridge(&mut self, lambda: f64) -> Result<f64, FittingError> {
+        assert!(lambda >= 0., "The ridge penalty must be non-negative.");
+        if let Some(entropy) = check_fit(&self.sampling_method, self.sampling_method.degree())? {
+            return Ok(entropy);
+        }
+        let naive_entropies = self.sampling_method.naive_entropies();
+        let coefficients = fit_polynomial(
+            &naive_entropies,
+            self.sampling_method.degree(),
+            |_| 1.,
+            lambda,
+        )?;
+        Ok(coefficients[0])
+    }
+
+    /// Estimates the entropy, expressed in the logarithm base `base`.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities.
+    pub fn entropy_with_base(&mut self, base: LogBase) -> Result<f64, FittingError> {
+        Ok(base.from_nats(self.entropy()?))
+    }
+
+    /// Estimates the perplexity, `exp(H)`, from the estimation of the entropy `H`.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities.
+    pub fn perplexity(&mut self) -> Result<f64, FittingError> {
+        self.entropy().map(f64::exp)
+    }
+
+    /// Estimates the entropy divided by its maximum, `H / ln(K)`,
+    /// where `K` is `support_size`, the number of possible elements.
+    ///
+    /// Unlike for [`NaiveEstimator::normalized_entropy`], the number of possible elements
+    /// can not be taken from the samples, since some may not have been observed.
+    ///
+    /// # Remarks
+    ///
+    /// If `support_size` is at most one, zero is returned.
+    /// The extrapolated entropy can exceed `ln(K)` (or be negative),
+    /// so the result is not clamped to `[0, 1]`.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities.
+    ///
+    /// [`NaiveEstimator::normalized_entropy`]: struct.NaiveEstimator.html#method.normalized_entropy
+    pub fn normalized_entropy(&mut self, support_size: usize) -> Result<f64, FittingError> {
+        if support_size <= 1 {
+            return Ok(0.);
+        }
+        Ok(self.entropy()? / (support_size as f64).ln())
+    }
+
+    /// Returns `reps` entropy estimations, each one computed from
+    /// a new set of naive entropy estimations.
+    ///
+    /// This is useful to compute any statistic of the estimation
+    /// (median, quantiles, etc.).
+    ///
+    /// # Remarks
+    ///
+    /// Replicates are only different if the sampling method is randomized,
+    /// like [`Bootstrap`].
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities in any of the replicates.
+    ///
+    /// [`Bootstrap`]: struct.Bootstrap.html
+    pub fn entropy_replicates(&mut self, reps: usize) -> Result<Vec<f64>, FittingError> {
+        (0..reps).map(|_| self.entropy()).collect()
+    }
+
+    /// Returns the mean of `reps` entropy estimations,
+    /// as computed by [`entropy_replicates`].
+    ///
+    /// For randomized sampling methods, like [`Bootstrap`],
+    /// this is a smoothed estimation with less variance than [`entropy`].
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities in any of the replicates.
+    ///
+    /// # Panics
+    ///
+    /// If `reps` is zero.
+    ///
+    /// [`entropy_replicates`]: #method.entropy_replicates
+    /// [`Bootstrap`]: struct.Bootstrap.html
+    /// [`entropy`]: #method.entropy
+    pub fn expected_entropy(&mut self, reps: usize) -> Result<f64, FittingError> {
+        assert!(reps > 0, "there must be at least one repetition");
+        let replicates = self.entropy_replicates(reps)?;
+        Ok(replicates.iter().sum::<f64>() / reps as f64)
+    }
+
+    /// Percentile confidence interval of level `1 - alpha` of the estimation,
+    /// returned as `(lower, point, upper)`.
+    ///
+    /// The whole estimation is repeated `replicates` times, as in [`entropy_replicates`],
+    /// and `lower`, `point` and `upper` are the `alpha / 2`, `1 / 2` and `1 - alpha / 2`
+    /// quantiles of the estimations (interpolating linearly between them).
+    ///
+    /// # Remarks
+    ///
+    /// The interval only reflects the randomness of the sampling method,
+    /// like [`Bootstrap`]. For deterministic sampling methods, like [`FixedPartition`],
+    /// the interval is degenerate: `lower`, `point` and `upper` are equal.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities in any of the replicates.
+    ///
+    /// # Panics
+    ///
+    /// If `replicates` is zero, or if `alpha` is not strictly between `0` and `1`.
+    ///
+    /// [`entropy_replicates`]: #method.entropy_replicates
+    /// [`Bootstrap`]: struct.Bootstrap.html
+    /// [`FixedPartition`]: struct.FixedPartition.html
+    pub fn entropy_ci(
+        &mut self,
+        replicates: usize,
+        alpha: f64,
+    ) -> Result<(f64, f64, f64), FittingError> {
+        assert!(replicates > 0, "there must be at least one replicate");
+        assert!(alpha > 0. && alpha < 1., "alpha must be in (0, 1)");
+        let mut estimations = self.entropy_replicates(replicates)?;
+        estimations.sort_by(|a, b| a.partial_cmp(b).unwrap()); // Never fails because estimations are finite
+
+        Ok((
+            quantile(&estimations, alpha / 2.),
+            quantile(&estimations, 0.5),
+            quantile(&estimations, 1. - alpha / 2.),
+        ))
+    }
+
+    /// Quantifies how much the estimation relies on extrapolation.
+    ///
+    /// It is the gap between the smallest `1/n` used in the fit and `1/n = 0`,
+    /// scaled by the slope of the fitted polynomial, i.e. `|p_1| / n_max`,
+    /// where `p_1` is the linear coefficient in `1/n` and
+    /// `n_max` is the biggest subsample size.
+    /// This is approximately the difference between the fitted entropy
+    /// at the biggest subsample size and the estimated entropy.
+    /// A large value warns that the estimation is speculative.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities.
+    pub fn extrapolation_distance(&mut self) -> Result<f64, FittingError> {
+        let coefficients = self.coefficients()?;
+        let slope = coefficients.get(1).copied().unwrap_or(0.);
+        let max_size = self
+            .sampling_method
+            .size_subsamples()
+            .into_iter()
+            .max()
+            .unwrap_or(1);
+        Ok(slope.abs() / max_size as f64)
+    }
+
+    /// Selects, among `candidates`, the number of groups which minimizes
+    /// the estimated mean squared error of the entropy estimation.
+    ///
+    /// For each candidate, the estimation is repeated a few times and
+    /// - the variance is estimated by the sample variance of the estimations,
+    /// - the bias is estimated by the size of the highest order term of the fit
+    ///   at the biggest subsample size, `|p_d| / n_max^d`, averaged over the repetitions.
+    ///
+    /// The estimated mean squared error is then `bias^2 + variance`.
+    ///
+    /// # Remarks
+    ///
+    /// Candidates that are not valid for the sampling method are ignored.
+    /// The number of groups of the sampling method is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If no candidate is valid, or if there are numerical instabilities for all of them.
+    pub fn optimal_num_groups(&mut self, candidates: &[usize]) -> Result<usize, FittingError> {
+        let original_num_groups = self.sampling_method.num_groups();
+
+        let mut optimal: Option<(usize, f64)> = None;
+        for &num_groups in candidates {
+            if self.sampling_method.set_num_groups(num_groups).is_err() {
+                continue;
+            }
+            if let Some(mse) = self.estimated_mse() {
+                let is_better = match optimal {
+                    Some((_, optimal_mse)) => mse < optimal_mse,
+                    None => true,
+                };
+                if is_better {
+                    optimal = Some((num_groups, mse));
+                }
+            }
+        }
+
+        // Never fails because it was a valid number of groups
+        let _ = self.sampling_method.set_num_groups(original_num_groups);
+        optimal
+            .map(|(num_groups, _)| num_groups)
+            .ok_or(FittingError::NoCandidate)
+    }
+
+    /// Estimated mean squared error of the entropy estimation,
+    /// as described in [`optimal_num_groups`].
+    ///
+    /// [`optimal_num_groups`]: #method.optimal_num_groups
+    fn estimated_mse(&mut self) -> Option<f64> {
+        let size_subsamples = self.sampling_method.size_subsamples();
+        if size_subsamples.contains(&0) {
+            return None;
+        }
+        let max_size = *size_subsamples.iter().max()? as f64;
+        let degree = self.sampling_method.degree();
+
+        let mut estimations = Vec::with_capacity(MSE_REPLICATES);
+        let mut bias = 0.;
+        for _ in 0..MSE_REPLICATES {
+            let coefficients = self.coefficients().ok()?;
+            estimations.push(coefficients[0]);
+            if degree > 0 {
+                bias += (coefficients[degree] / max_size.powi(degree as i32)).abs();
+            }
+        }
+        bias /= MSE_REPLICATES as f64;
+
+        let mean = estimations.iter().sum::<f64>() / MSE_REPLICATES as f64;
+        let variance = estimations
+            .iter()
+            .map(|estimation| (estimation - mean).powi(2))
+            .sum::<f64>()
+            / (MSE_REPLICATES - 1) as f64;
+
+        let mse = bias.powi(2) + variance;
+        if mse.is_finite() {
+            Some(mse)
+        } else {
+            None
+        }
+    }
+
+    /// Computes naive entropy estimations and returns them as the points
+    /// `(1/n, value)` to which [`entropy`] fits a polynomial,
+    /// where `n` is the size of the subsample.
+    ///
+    /// Useful to plot or reproduce the extrapolation externally.
+    /// New subsamples are drawn on each call, as in [`entropy`].
+    ///
+    /// [`entropy`]: #method.entropy
+    pub fn fit_points(&mut self) -> Vec<(f64, f64)> {
+        self.sampling_method
+            .naive_entropies()
+            .into_iter()
+            .map(|(size, value)| (1. / size as f64, value))
+            .collect()
+    }
+
+    /// Returns the naive entropy of all available samples, pooled together.
+    ///
+    /// This is the baseline the extrapolated estimation improves on.
+    pub fn plug_in_entropy(&self) -> f64 {
+        let pooled_unnorm_distr = self.sampling_method.pooled_unnorm_distr();
+        // Never fails because sampling methods have at least one sample.
+        NaiveEstimator::new_unchecked(&pooled_unnorm_distr).entropy()
+    }
+
+    /// Returns a confidence interval for the entropy, at level `1 - alpha`,
+    /// using the asymptotic normal approximation.
+    ///
+    /// The interval is centered at [`entropy`], with half width `z * se`,
+    /// where `z` is the `1 - alpha / 2` quantile of the standard normal distribution
+    /// and `se` the asymptotic standard error of the naive entropy of the pooled samples,
+    /// `sqrt((Σ p_i ln(p_i)^2 - H^2) / N)`.
+    ///
+    /// # Remarks
+    ///
+    /// No resampling is needed to compute the width, but the normal approximation
+    /// is only valid in the well-sampled regime, when the number of samples is large
+    /// compared to the number of distinct elements.
+    /// In the undersampled regime, the width is unreliable (it vanishes
+    /// for a uniform distribution) and resampling [`entropy_replicates`] is preferable.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities.
+    ///
+    /// # Panics
+    ///
+    /// If `alpha` is not strictly between `0` and `1`.
+    ///
+    /// [`entropy`]: #method.entropy
+    /// [`entropy_replicates`]: #method.entropy_replicates
+    pub fn entropy_normal_ci(&mut self, alpha: f64) -> Result<(f64, f64), FittingError> {
+        assert!(alpha > 0. && alpha < 1., "alpha must be in (0, 1)");
+        let z = crate::utils::normal_quantile(1. - alpha / 2.);
+        let pooled_unnorm_distr = self.sampling_method.pooled_unnorm_distr();
+        let standard_error = NaiveEstimator::new_unchecked(&pooled_unnorm_distr)
+            .entropy_variance()
+            .sqrt();
+
+        let entropy = self.entropy()?;
+        Ok((entropy - z * standard_error, entropy + z * standard_error))
+    }
+
+    /// Estimates the subsample size beyond which the fitted polynomial
+    /// is within `tol` of the estimated entropy.
+    ///
+    /// This is the smallest `n` such that `Σ_{k >= 1} |p_k| / n^k <= tol`,
+    /// where `p_k` are the coefficients of the fitted polynomial in `1/n`.
+    /// The left hand side bounds the difference between the fitted curve at `1/n`
+    /// and at `0`, so it indicates roughly how many samples would make
+    /// the naive entropy "good enough".
+    ///
+    /// Returns `None` if there are numerical instabilities (including coefficients
+    /// that are not finite), if `tol` is not positive or if the size does not fit in `usize`.
+    pub fn saturation_size(&mut self, tol: f64) -> Option<usize> {
+        if tol.is_nan() || tol <= 0. {
+            return None;
+        }
+        let coefficients = self.coefficients().ok()?;
+        if !coefficients
+            .iter()
+            .all(|coefficient| coefficient.is_finite())
+        {
+            return None;
+        }
+        let deviation = |size: usize| -> f64 {
+            let x = 1. / size as f64;
+            coefficients
+                .iter()
+                .skip(1)
+                .rev()
+                .fold(0., |acc, coefficient| (acc + coefficient.abs()) * x)
+        };
+
+        // The deviation is decreasing in the size: double, then bisect
+        let mut upper = 1;
+        while deviation(upper) > tol {
+            upper = upper.checked_mul(2)?;
+        }
+        let mut lower = upper / 2;
+        while upper - lower > 1 {
+            let middle = lower + (upper - lower) / 2;
+            if deviation(middle) > tol {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+        Some(upper)
+    }
+
+    /// Fits a polynomial in `1/n` to the naive entropy estimations
+    /// and returns its coefficients, from the constant term upwards.
+    fn coefficients(&mut self) -> Result<Vec<f64>, FittingError> {
+        Ok(self.entropy_fit()?.coefficients)
+    }
+}
+
+/// Dispersion of entropy estimations across seeds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeedSensitivity {
+    /// Mean of the estimations.
+    pub mean: f64,
+    /// Standard deviation of the estimations.
+    pub std: f64,
+    /// Difference between the largest and the smallest estimations.
+    pub range: f64,
+}
+
+/// # Randomized methods
+impl<M> Estimator<M>
+where
+    M: SamplingMethod + Reseed,
+{
+    /// Estimates the entropy once per seed, reseeding the sampling method each time,
+    /// and reports the dispersion of the estimations.
+    ///
+    /// A large dispersion suggests using more groups.
+    ///
+    /// # Remarks
+    ///
+    /// The sampling method must be randomized, i.e. implement [`Reseed`].
+    /// Afterwards, the sampling method is left seeded with the last seed.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities for any of the seeds.
+    ///
+    /// # Panics
+    ///
+    /// If `seeds` is empty.
+    ///
+    /// [`Reseed`]: trait.Reseed.html
+    pub fn seed_sensitivity(&mut self, seeds: &[u64]) -> Result<SeedSensitivity, FittingError> {
+        assert!(!seeds.is_empty(), "there must be at least one seed");
+        let estimations = seeds
+            .iter()
+            .map(|&seed| {
+                self.sampling_method.reseed(seed);
+                self.entropy()
+            })
+            .collect::<Result<Vec<f64>, FittingError>>()?;
+
+        let num_estimations = estimations.len() as f64;
+        let mean = estimations.iter().sum::<f64>() / num_estimations;
+        let variance = estimations
+            .iter()
+            .map(|estimation| (estimation - mean).powi(2))
+            .sum::<f64>()
+            / num_estimations;
+        let max = estimations
+            .iter()
+            .cloned()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let min = estimations.iter().cloned().fold(f64::INFINITY, f64::min);
+
+        Ok(SeedSensitivity {
+            mean,
+            std: variance.sqrt(),
+            range: max - min,
+        })
+    }
+}
+
+/// # Estimation
+impl<M, F> Estimator<M, F>
+where
+    M: SamplingMethod,
+    F: RealField + Copy,
+{
+    /// Estimates the entropy of the underlying distribution,
+    /// known only through the empirical unnormalized distribution.
+    ///
+    /// # Remarks
+    ///
+    /// If a single element was observed, all naive entropies are zero
+    /// and zero is returned exactly, without fitting.
+    ///
+    /// # Errors
+    ///
+    /// If there are fewer distinct subsample sizes than the degree plus one
+    /// (see [`can_fit`]), or if there are numerical instabilities.
+    ///
+    /// [`can_fit`]: trait.SamplingMethod.html#method.can_fit
+    pub fn entropy(&mut self) -> Result<F, FittingError> {
+        if let Some(entropy) = check_fit(&self.sampling_method, self.sampling_method.degree())? {
+            return Ok(nalgebra::convert(entropy));
+        }
+        let naive_entropies = self.sampling_method.naive_entropies();
+        let coefficients =
+            fit_polynomial(&naive_entropies, self.sampling_method.degree(), |_| 1., 0.)?;
+        Ok(coefficients[0])
+    }
+
+    /// Same as [`entropy`], but each naive entropy estimation is folded,
+    /// as soon as it is computed, into the normal equations of the least squares fit.
+    ///
+    /// Only a `(degree + 1) × (degree + 1)` system is stored and solved,
+    /// whatever the number of subsamples, and estimations are taken from
+    /// [`naive_entropies_iter`].
+    ///
+    /// # Remarks
+    ///
+    /// The normal equations are worse conditioned than the decomposition used by [`entropy`],
+    /// so results can differ slightly for high degrees or a wide range of subsample sizes.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`entropy`].
+    ///
+    /// [`entropy`]: #method.entropy
+    /// [`naive_entropies_iter`]: trait.SamplingMethod.html#method.naive_entropies_iter
+    pub fn entropy_streaming(&mut self) -> Result<F, FittingError> {
+        let degree = self.sampling_method.degree();
+        if let Some(entropy) = check_fit(&self.sampling_method, degree)? {
+            return Ok(nalgebra::convert(entropy));
+        }
+        let mut gram = DMatrix::<F>::zeros(degree + 1, degree + 1);
+        let mut rhs = DVector::<F>::zeros(degree + 1);
+        let mut row = DVector::<F>::zeros(degree + 1);
+        fold_normal_equations(
+            self.sampling_method.naive_entropies_iter(),
+            &mut row,
+            |row, y| {
+                gram.ger(F::one(), row, row, F::one());
+                rhs.axpy(y, row, F::one());
+            },
+        )?;
+
+        // Columns `n^{1 - k}` have very different magnitudes, so the system is equilibrated
+        let scales = gram.diagonal().map(|entry| F::one() / entry.sqrt());
+        let scaled_gram = DMatrix::from_fn(degree + 1, degree + 1, |i, j| {
+            gram[(i, j)] * scales[i] * scales[j]
+        });
+        let scaled_rhs = rhs.component_mul(&scales);
+        let solution = scaled_gram
+            .cholesky()
+            .ok_or(FittingError::Singular)?
+            .solve(&scaled_rhs);
+        Ok(solution[0] * scales[0])
+    }
+}
+
+/// Estimates the entropy with both fitting backends, [`Estimator`] and [`DirectEstimator`],
+/// on copies of `sampling_method`, and returns both estimations and their absolute difference.
+///
+/// Both copies draw the same subsamples, so any difference comes from the fit,
+/// provided that cloning the random number generator copies its state.
+/// This is not the case for `ThreadRng`, whose clones share the same generator.
+///
+/// # Remarks
+///
+/// [`Estimator`] fits `n H(n)` while [`DirectEstimator`] fits `H(n)`,
+/// so the points of size `n` weigh `n^2` times more in the former.
+/// Both fits are the same (up to rounding) only if the polynomial passes
+/// through the mean of each group, i.e. if there are as many distinct subsample sizes
+/// as coefficients. Otherwise, a large difference warns that the estimation
+/// depends on how the points are weighted.
+///
+/// # Errors
+///
+/// If either estimation fails.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::{compare_backends, Bootstrap};
+/// # use rand::SeedableRng;
+/// let rng = rand_pcg::Pcg32::seed_from_u64(1);
+/// let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng).unwrap();
+/// let (estimator, direct, difference) = compare_backends(&bootstrap).unwrap();
+/// assert!(difference < 1e-9);
+/// ```
+///
+/// [`Estimator`]: struct.Estimator.html
+/// [`DirectEstimator`]: struct.DirectEstimator.html
+pub fn compare_backends<M>(sampling_method: &M) -> Result<(f64, f64, f64), FittingError>
+where
+    M: SamplingMethod + Clone,
+{
+    let estimation = Estimator::new(sampling_method.clone()).entropy()?;
+    let direct_estimation = DirectEstimator::new(sampling_method.clone()).entropy()?;
+    Ok((
+        estimation,
+        direct_estimation,
+        (estimation - direct_estimation).abs(),
+    ))
+}
+
+/// Returns whether a single element was observed,
+/// in which case the entropy is exactly zero.
+fn single_element<M>(sampling_method: &M) -> bool
+where
+    M: SamplingMethod,
+{
+    let pooled_unnorm_distr = sampling_method.pooled_unnorm_distr();
+    NaiveEstimator::new_unchecked(&pooled_unnorm_distr).support_size() == 1
+}
+
+/// Checks, before drawing any subsample, whether a polynomial of degree `degree` is needed.
+///
+/// Returns `Some(0.)` if a single element was observed, in which case the entropy
+/// is exactly zero and no fit is needed, see [`single_element`].
+///
+/// # Errors
+///
+/// If there are fewer distinct subsample sizes than `degree` plus one,
+/// see [`SamplingMethod::can_fit`].
+///
+/// [`single_element`]: fn.single_element.html
+/// [`SamplingMethod::can_fit`]: trait.SamplingMethod.html#method.can_fit
+fn check_fit<M>(sampling_method: &M, degree: usize) -> Result<Option<f64>, FittingError>
+where
+    M: SamplingMethod,
+{
+    if single_element(sampling_method) {
+        Ok(Some(0.))
+    } else if num_distinct_sizes(sampling_method) > degree {
+        Ok(None)
+    } else {
+        Err(FittingError::DegeneratePoints)
+    }
+}
+
+/// Weighted least squares fit of `n H(n)` by `Σ_k c_k n^{1 - k}`, for `k` up to `degree`,
+/// where the point of size `n` has weight `weight(n)`
+/// and `ridge Σ_{k >= 1} c_k^2` is added to the squared error.
+/// Returns the coefficients `c_k`.
+///
+/// There must be at least `degree + 1` distinct sizes,
+/// otherwise the columns of the design matrix are collinear.
+fn fit_polynomial<F, W>(
+    naive_entropies: &[(usize, f64)],
+    degree: usize,
+    weight: W,
+    ridge: f64,
+) -> Result<Vec<F>, FittingError>
+where
+    F: RealField + Copy,
+    W: Fn(usize) -> f64,
+{
+    let mut distinct_sizes: Vec<usize> = naive_entropies.iter().map(|&(size, _)| size).collect();
+    distinct_sizes.sort_unstable();
+    distinct_sizes.dedup();
+    if distinct_sizes.len() <= degree {
+        return Err(FittingError::DegeneratePoints);
     }
-    /// Estimates the entropy of the underlying distribution,
-    /// known only through the empirical unnormalized distribution.
-    ///
-    /// # Errors
-    ///
-    /// If there are numerical instabilities.
-    pub fn entropy(&mut self) -> Result<f64, FittingError> {
-        let (size_subsamples_dup, scaled_naive_entropies): (Vec<_>, Vec<_>) = self
-            .sampling_method
-            .naive_entropies()
-            .into_iter()
-            .map(|(size, value)| (size, value * size as f64))
-            .unzip();
-
-        // Fitting a polynomial
-        let y = DVector::from_vec(scaled_naive_entropies);
-        let x = DMatrix::<f64>::from_fn(
-            self.sampling_method.total_samples(),
-            self.sampling_method.degree() + 1,
-            |r, c| (size_subsamples_dup[r] as f64).powi(1 - c as i32),
-        );
 
-        // Least squares for `x ? = y`
-        let x_t = x.transpose();
-        let b = x_t.clone() * y;
-        let a = x_t * x;
+    let row_scales: Vec<F> = naive_entropies
+        .iter()
+        .map(|&(size, _)| nalgebra::convert(weight(size).sqrt()))
+        .collect();
+    let (size_subsamples_dup, scaled_naive_entropies): (Vec<F>, Vec<F>) = naive_entropies
+        .iter()
+        .zip(&row_scales)
+        .map(|(&(size, value), &row_scale)| {
+            let size: F = nalgebra::convert(size as f64);
+            (size, nalgebra::convert::<f64, F>(value) * size * row_scale)
+        })
+        .unzip();
 
-        match a.lu().solve(&b) {
-            Some(polynomial) => Ok(polynomial[0]),
-            None => Err(FittingError),
+    // Fitting a polynomial, the penalty being extra rows `sqrt(ridge) c_k = 0`
+    let num_points = naive_entropies.len();
+    let num_rows = if ridge > 0. {
+        num_points + degree
+    } else {
+        num_points
+    };
+    let ridge_scale: F = nalgebra::convert(ridge.sqrt());
+    let y = DVector::from_fn(num_rows, |r, _| {
+        scaled_naive_entropies
+            .get(r)
+            .copied()
+            .unwrap_or_else(F::zero)
+    });
+    let x = DMatrix::<F>::from_fn(num_rows, degree + 1, |r, c| {
+        if r < num_points {
+            size_subsamples_dup[r].powi(1 - c as i32) * row_scales[r]
+        } else if c == r - num_points + 1 {
+            ridge_scale
+        } else {
+            F::zero()
         }
+    });
+
+    // Least squares for `x ? = y`, solving `r ? = q^T y` where `x = q r`.
+    // Unlike the normal equations, this does not square the condition number of `x`.
+    let qr = x.qr();
+    let q_t_y = qr.q().transpose() * y;
+
+    Ok(qr
+        .r()
+        .solve_upper_triangular(&q_t_y)
+        .ok_or(FittingError::Singular)?
+        .iter()
+        .copied()
+        .collect())
+}
+
+/// Folds naive entropy estimations into the normal equations of the fit of [`fit_polynomial`],
+/// without weights nor ridge penalty.
+///
+/// For each point, `row` is filled with `n^{1 - k}`, for `k` up to its length minus one,
+/// and `accumulate` is called with it and the target `n H(n)`.
+///
+/// # Errors
+///
+/// If there are fewer distinct sizes than the length of `row`.
+fn fold_normal_equations<F, R, S, I, A>(
+    naive_entropies: I,
+    row: &mut Matrix<F, R, U1, S>,
+    mut accumulate: A,
+) -> Result<(), FittingError>
+where
+    F: RealField + Copy,
+    R: Dim,
+    S: StorageMut<F, R>,
+    I: Iterator<Item = (usize, f64)>,
+    A: FnMut(&Matrix<F, R, U1, S>, F),
+{
+    let mut distinct_sizes = Vec::new();
+    for (size, value) in naive_entropies {
+        if !distinct_sizes.contains(&size) {
+            distinct_sizes.push(size);
+        }
+        let size_float: F = nalgebra::convert(size as f64);
+        for (k, entry) in row.iter_mut().enumerate() {
+            *entry = size_float.powi(1 - k as i32);
+        }
+        accumulate(row, nalgebra::convert::<f64, F>(value) * size_float);
+    }
+    if distinct_sizes.len() < row.len() {
+        return Err(FittingError::DegeneratePoints);
     }
+    Ok(())
+}
+
+/// Quantile of order `q` of `sorted`, interpolating linearly between values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let below = position.floor() as usize;
+    let above = position.ceil() as usize;
+    sorted[below] + (position - below as f64) * (sorted[above] - sorted[below])
 }
 
 /// # Getters
 ///
 /// Get the underlying sampling method.
-impl<M> Estimator<M>
+impl<M, F> Estimator<M, F>
 where
     M: SamplingMethod,
 {
@@ -107,20 +989,116 @@ where
     pub fn sampling_method_mut(&mut self) -> &mut M {
         &mut self.sampling_method
     }
+
+    /// Returns the degree of the polynomial used to fit the naive entropy estimations.
+    pub fn degree(&self) -> usize {
+        self.sampling_method.degree()
+    }
+
+    /// Returns the number of groups of subsamples.
+    pub fn num_groups(&self) -> usize {
+        self.sampling_method.num_groups()
+    }
+}
+
+/// # Tuning
+///
+/// Change the parameters of the underlying sampling method.
+impl<M, F> Estimator<M, F>
+where
+    M: SamplingMethod,
+{
+    /// Changes the degree of the polynomial used to fit the naive entropy estimations.
+    ///
+    /// # Errors
+    ///
+    /// If the sampling method rejects the degree (for example, if it is too high
+    /// for the number of groups), with the reason given by the sampling method.
+    pub fn set_degree(&mut self, degree: usize) -> Result<&mut Self, ParameterError> {
+        self.sampling_method
+            .set_degree(degree)
+            .map_err(|error| ParameterError::Degree(error.to_string()))?;
+        Ok(self)
+    }
+
+    /// Changes the number of groups of subsamples.
+    ///
+    /// # Errors
+    ///
+    /// If the sampling method rejects the number of groups, with the reason given by the sampling method.
+    pub fn set_num_groups(&mut self, num_groups: usize) -> Result<&mut Self, ParameterError> {
+        self.sampling_method
+            .set_num_groups(num_groups)
+            .map_err(|error| ParameterError::NumGroups(error.to_string()))?;
+        Ok(self)
+    }
 }
 
 /// # Transformations
-impl<M> Estimator<M> {
-    pub fn set_sampling_method<M2>(self, other: M2) -> Estimator<M2>
+impl<M, F> Estimator<M, F> {
+    pub fn set_sampling_method<M2>(self, other: M2) -> Estimator<M2, F>
     where
         M2: SamplingMethod,
     {
         Estimator {
             sampling_method: other,
+            float: PhantomData,
+        }
+    }
+
+    /// Replaces the sampling method in place, returning the previous one.
+    ///
+    /// Useful to reuse one `Estimator` over many distributions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::{Bootstrap, Estimator, SamplingMethod};
+    /// let mut estimator = Estimator::from([1, 2, 3, 4, 5, 6]);
+    /// for unnorm_distr in [[8, 1, 1], [3, 3, 4]] {
+    ///     let bootstrap = Bootstrap::new(&unnorm_distr, 3, 2, rand::thread_rng()).unwrap();
+    ///     estimator.replace_sampling_method(bootstrap);
+    ///     println!("Entropy estimation: {:?}", estimator.entropy());
+    /// }
+    /// ```
+    pub fn replace_sampling_method(&mut self, other: M) -> M {
+        core::mem::replace(&mut self.sampling_method, other)
+    }
+
+    /// Changes the floating point type of the estimation.
+    ///
+    /// Naive entropies are computed in `f64`,
+    /// only the polynomial fit is done in the new type.
+    pub fn with_float<G>(self) -> Estimator<M, G>
+    where
+        G: RealField + Copy,
+    {
+        Estimator {
+            sampling_method: self.sampling_method,
+            float: PhantomData,
         }
     }
 }
 
+impl<M> EntropyEstimate for Estimator<M>
+where
+    M: SamplingMethod,
+{
+    fn entropy(&mut self) -> Result<f64, FittingError> {
+        Estimator::entropy(self)
+    }
+}
+
+/// Summary of the configuration of the sampling method.
+impl<M, F> fmt::Display for Estimator<M, F>
+where
+    M: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Estimator({})", self.sampling_method)
+    }
+}
+
 impl<M> From<M> for Estimator<M>
 where
     M: SamplingMethod,
@@ -130,6 +1108,87 @@ where
     }
 }
 
+/// # Reproducible constructors
+impl Estimator<Bootstrap<Pcg32>> {
+    /// Constructs a new `Estimator` from an unnormalized distribution,
+    /// using a random number generator seeded with `seed`.
+    ///
+    /// For a fixed unnormalized distribution, the seed fully determines
+    /// the estimation (up to floating point rounding).
+    ///
+    /// # Remarks
+    ///
+    /// Default values are given to tunable parameters, as in `From<[usize; N]>`.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples for the default number of groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::Estimator;
+    /// let unnorm_distr = [1, 2, 3, 4, 5, 6];
+    /// let first = Estimator::from_seed(&unnorm_distr, 1).entropy().unwrap();
+    /// let second = Estimator::from_seed(&unnorm_distr, 1).entropy().unwrap();
+    /// assert!((first - second).abs() < 1e-12);
+    /// ```
+    pub fn from_seed(unnorm_distr: &[usize], seed: u64) -> Self {
+        let sampling_method = Bootstrap::new(
+            unnorm_distr,
+            DEFAULT_NUM_GROUPS,
+            DEFAULT_DEGREE,
+            Pcg32::seed_from_u64(seed),
+        )
+        .unwrap();
+        Estimator::new(sampling_method)
+    }
+}
+
+/// # Fallible constructors
+impl Estimator<Bootstrap<ThreadRng>> {
+    /// Constructs a new `Estimator` from an unnormalized distribution,
+    /// with default values for tunable parameters, as in `From<[usize; N]>`.
+    ///
+    /// Unlike the `From` conversions, small inputs give an error instead of a panic.
+    ///
+    /// # Errors
+    ///
+    /// If there are too few samples for the default number of groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::Estimator;
+    /// assert!(Estimator::try_from_distr(&[1, 1, 1]).is_err());
+    /// assert!(Estimator::try_from_distr(&[1, 2, 3, 4, 5, 6]).is_ok());
+    /// ```
+    pub fn try_from_distr(unnorm_distr: &[usize]) -> Result<Self, ConstructionError> {
+        let sampling_method = Bootstrap::new(
+            unnorm_distr,
+            DEFAULT_NUM_GROUPS,
+            DEFAULT_DEGREE,
+            rand::thread_rng(),
+        )?;
+        Ok(Estimator::new(sampling_method))
+    }
+
+    /// Constructs a new `Estimator` directly from samples,
+    /// with default values for tunable parameters, as in `From<&[T]>`.
+    ///
+    /// Duplicated samples will be counted to construct an unnormalized distribution.
+    ///
+    /// # Errors
+    ///
+    /// If there are too few samples for the default number of groups.
+    pub fn try_from_samples<T>(samples: &[T]) -> Result<Self, ConstructionError>
+    where
+        T: Hash + Eq + Clone,
+    {
+        Estimator::try_from_distr(&crate::count_dup(samples))
+    }
+}
+
 impl<const N: usize> From<[usize; N]> for Estimator<Bootstrap<ThreadRng>> {
     /// Performs the conversion from an unnormalized distribution.
     ///
@@ -137,6 +1196,13 @@ impl<const N: usize> From<[usize; N]> for Estimator<Bootstrap<ThreadRng>> {
     ///
     /// This gives an easy entry point for using `Estimator`,
     /// but be aware that default values are given to tunable parameters.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples for the default number of groups,
+    /// see [`try_from_distr`] for a fallible alternative.
+    ///
+    /// [`try_from_distr`]: struct.Estimator.html#method.try_from_distr
     fn from(unnorm_distr: [usize; N]) -> Self {
         let sampling_method = Bootstrap::new(
             &unnorm_distr,
@@ -161,6 +1227,13 @@ where
     ///
     /// This gives an easy entry point for using `Estimator`,
     /// but be aware that default values are given to tunable parameters.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples for the default number of groups,
+    /// see [`try_from_samples`] for a fallible alternative.
+    ///
+    /// [`try_from_samples`]: struct.Estimator.html#method.try_from_samples
     fn from(samples: &[T]) -> Self {
         let unnorm_distr = crate::count_dup(&samples);
         let sampling_method = Bootstrap::new(
@@ -186,6 +1259,13 @@ where
     ///
     /// This gives an easy entry point for using `Estimator`,
     /// but be aware that default values are given to tunable parameters.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples for the default number of groups,
+    /// see [`try_from_samples`] for a fallible alternative.
+    ///
+    /// [`try_from_samples`]: struct.Estimator.html#method.try_from_samples
     fn from(samples: Vec<T>) -> Self {
         <Estimator<Bootstrap<ThreadRng>> as From<&[T]>>::from(&samples)
     }
@@ -197,7 +1277,11 @@ mod tests {
     use float_eq::assert_float_eq;
     use test_case::test_case;
 
-    use crate::test::rng;
+    use crate::{
+        test::{fixed_partition, rng, Linear},
+        utils::count_dup_ordered,
+        FixedPartition,
+    };
 
     #[test_case([8]; "one_sample")]
     #[test_case([1, 2, 3, 4, 5, 6]; "[usize; N]")]
@@ -210,9 +1294,66 @@ mod tests {
         Estimator::from(source);
     }
 
+    #[test]
+    fn set_parameters() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = Estimator::new(bootstrap);
+
+        estimator.set_num_groups(4).unwrap().set_degree(3).unwrap();
+        assert_eq!(estimator.num_groups(), 4);
+        assert_eq!(estimator.degree(), 3);
+    }
+
+    #[test]
+    fn set_parameters_invalid() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = Estimator::new(bootstrap);
+
+        assert!(matches!(
+            estimator.set_degree(3),
+            Err(ParameterError::Degree(_))
+        ));
+        assert!(matches!(
+            estimator.set_num_groups(2),
+            Err(ParameterError::NumGroups(_))
+        ));
+        assert_eq!(estimator.degree(), 2);
+        assert_eq!(estimator.num_groups(), 3);
+    }
+
+    #[test]
+    fn replace_sampling_method() {
+        let first = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let second = Bootstrap::new(&[10, 20], 3, 2, rng(1)).unwrap();
+        let mut estimator = Estimator::new(first);
+
+        let previous = estimator.replace_sampling_method(second);
+        assert_eq!(previous.pooled_unnorm_distr(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            estimator.sampling_method().pooled_unnorm_distr(),
+            vec![10, 20]
+        );
+    }
+
+    #[test_case(&[1, 1, 1], false; "too_few")]
+    #[test_case(&[8], true; "one_element")]
+    #[test_case(&[1, 2, 3, 4, 5, 6], true; "increasing")]
+    fn try_from_distr(unnorm_distr: &[usize], expected_ok: bool) {
+        assert_eq!(Estimator::try_from_distr(unnorm_distr).is_ok(), expected_ok);
+    }
+
+    #[test]
+    fn try_from_samples() {
+        assert!(Estimator::try_from_samples(&['a', 'b', 'c']).is_err());
+
+        let samples = ['a', 'b', 'c', 'd', 'd', 'e', 'e', 'e'];
+        let estimator = Estimator::try_from_samples(&samples).unwrap();
+        assert_eq!(estimator.sampling_method().dataset_size(), samples.len());
+    }
+
     /// Value stability of implementation
-    #[test_case([1, 2, 3, 4, 5, 6], 1.9511041580553; "increasing")]
-    #[test_case(vec!['a', 'b', 'c', 'd', 'd', 'e', 'e', 'e'], 1.9511041580553; "letters")]
+    #[test_case([1, 2, 3, 4, 5, 6], 1.8136715470699; "increasing")]
+    #[test_case(vec!['a', 'b', 'c', 'd', 'd', 'e', 'e', 'e'], 1.8136715470699; "letters")]
     fn entropy<T>(source: T, expected: f64)
     where
         Estimator<Bootstrap<ThreadRng>>: From<T>,
@@ -225,4 +1366,514 @@ mod tests {
 
         assert_float_eq!(estimator.entropy().unwrap(), expected, abs <= 1e-6);
     }
+
+    #[test_case(&[1, 2, 3, 4, 5, 6]; "increasing")]
+    #[test_case(&[10; 20]; "uniform")]
+    #[test_case(&[500, 20, 10, 1, 1]; "skewed")]
+    fn with_float(unnorm_distr: &[usize]) {
+        let bootstrap = Bootstrap::new(unnorm_distr, 3, 2, rng(1)).unwrap();
+        let expected = Estimator::new(bootstrap).entropy().unwrap();
+        let bootstrap = Bootstrap::new(unnorm_distr, 3, 2, rng(1)).unwrap();
+        let value = Estimator::new(bootstrap)
+            .with_float::<f32>()
+            .entropy()
+            .unwrap();
+
+        assert_float_eq!(value as f64, expected, abs <= 1e-4);
+    }
+
+    #[test]
+    fn entropy_replicates() {
+        let reps = 10;
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = Estimator::new(bootstrap);
+        let replicates = estimator.entropy_replicates(reps).unwrap();
+
+        assert_eq!(replicates.len(), reps);
+        assert!(replicates.iter().all(|value| value.is_finite()));
+        assert!(replicates.iter().any(|value| value != &replicates[0]));
+    }
+
+    #[test]
+    fn entropy_single_element() {
+        for seed in 0..10 {
+            let value = Estimator::from_seed(&[8], seed).entropy().unwrap();
+            assert_eq!(value, 0.);
+        }
+        let samples = [3; 6];
+        let fixed = FixedPartition::new(&samples, &[3, 2, 1], &[1, 1, 1], 2).unwrap();
+        assert_eq!(Estimator::new(fixed).entropy().unwrap(), 0.);
+    }
+
+    #[test]
+    fn entropy_single_element_every_path() {
+        let mut estimator = Estimator::from_seed(&[8], 1);
+        let value = estimator.entropy().unwrap();
+        assert_eq!(value, 0.);
+
+        assert_eq!(estimator.entropy_fit().unwrap().estimate, value);
+        assert_eq!(estimator.entropy_weighted(|n| n as f64).unwrap(), value);
+        assert_eq!(estimator.entropy_ridge(1.).unwrap(), value);
+        assert_eq!(estimator.entropy_streaming().unwrap(), value);
+
+        let bootstrap = estimator.sampling_method().clone();
+        let mut fixed_degree = FixedDegreeEstimator::<_, 2>::new(bootstrap);
+        assert_eq!(fixed_degree.entropy().unwrap(), value);
+    }
+
+    #[test_case(&[1, 2, 3, 4, 5, 6], 3, 2; "quadratic")]
+    #[test_case(&[10, 20, 30, 40, 5, 5], 4, 3; "cubic")]
+    fn compare_backends_agree(unnorm_distr: &[usize], num_groups: usize, degree: usize) {
+        let bootstrap = Bootstrap::new(unnorm_distr, num_groups, degree, rng(1)).unwrap();
+        let (estimation, direct_estimation, difference) = compare_backends(&bootstrap).unwrap();
+
+        assert_float_eq!(estimation, direct_estimation, abs <= 1e-9);
+        assert_eq!(difference, (estimation - direct_estimation).abs());
+    }
+
+    /// A line through points on a curve depends on their weights
+    #[test]
+    fn compare_backends_diverge() {
+        let (_, _, difference) = compare_backends(&negative_partition()).unwrap();
+
+        assert!(difference > 0.1);
+    }
+
+    #[test]
+    fn entropy_estimate() {
+        let unnorm_distr = [1, 2, 3, 4, 5, 6];
+        let mut estimators: Vec<Box<dyn EntropyEstimate>> = vec![
+            Box::new(Estimator::from_seed(&unnorm_distr, 1)),
+            Box::new(DirectEstimator::from_seed(&unnorm_distr, 1)),
+            Box::new(FixedDegreeEstimator::<_, 2>::new(
+                Bootstrap::new(&unnorm_distr, 3, 2, Pcg32::seed_from_u64(1)).unwrap(),
+            )),
+        ];
+        let expected = Estimator::from_seed(&unnorm_distr, 1).entropy().unwrap();
+
+        for estimator in estimators.iter_mut() {
+            assert_float_eq!(estimator.entropy().unwrap(), expected, abs <= 1e-9);
+        }
+    }
+
+    #[test]
+    fn display() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let estimator = Estimator::new(bootstrap);
+        let summary = estimator.to_string();
+
+        assert!(summary.starts_with("Estimator(Bootstrap("));
+        assert!(summary.contains("num_groups: 3"));
+        assert!(summary.contains("degree: 2"));
+        assert!(summary.contains("samples_rep: [1, 4, 16]"));
+    }
+
+    #[test]
+    fn entropy_replicates_reproducible() {
+        let unnorm_distr = [1, 2, 3, 4, 5, 6];
+        let replicates = Estimator::from_seed(&unnorm_distr, 7)
+            .entropy_replicates(5)
+            .unwrap();
+        let expected = Estimator::from_seed(&unnorm_distr, 7)
+            .entropy_replicates(5)
+            .unwrap();
+
+        assert_eq!(replicates, expected);
+    }
+
+    #[test_case(&[1, 2, 3, 4, 5, 6], 3, 2; "increasing")]
+    #[test_case(&[10; 20], 3, 2; "uniform")]
+    #[test_case(&[500, 20, 10, 1, 1], 4, 2; "skewed")]
+    #[test_case(&[3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5], 5, 3; "cubic")]
+    fn entropy_streaming(unnorm_distr: &[usize], num_groups: usize, degree: usize) {
+        let bootstrap = Bootstrap::new(unnorm_distr, num_groups, degree, rng(1)).unwrap();
+        let expected = Estimator::new(bootstrap).entropy().unwrap();
+        let bootstrap = Bootstrap::new(unnorm_distr, num_groups, degree, rng(1)).unwrap();
+        let value = Estimator::new(bootstrap).entropy_streaming().unwrap();
+
+        assert_float_eq!(value, expected, abs <= 1e-9);
+    }
+
+    #[test]
+    fn entropy_streaming_fixed_partition() {
+        let expected = Estimator::new(negative_partition()).entropy().unwrap();
+        let value = Estimator::new(negative_partition())
+            .entropy_streaming()
+            .unwrap();
+        assert_float_eq!(value, expected, abs <= 1e-9);
+
+        let samples = [0, 1, 0, 1, 1, 1, 0];
+        let fixed = FixedPartition::new(&samples, &[2, 2, 2], &[1, 1, 1], 2).unwrap();
+        let error = Estimator::new(fixed).entropy_streaming().unwrap_err();
+        assert!(matches!(error, FittingError::DegeneratePoints));
+    }
+
+    /// Subsamples are taken from the end: the largest one is constant
+    /// while the smaller ones are diverse, so the extrapolation is negative.
+    fn negative_partition() -> FixedPartition {
+        let samples = [3, 4, 1, 2, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0];
+        FixedPartition::new(&samples, &[8, 4, 2], &[1, 1, 1], 1).unwrap()
+    }
+
+    /// Null weights zero out the design matrix
+    #[test]
+    fn singular() {
+        let error = Estimator::new(fixed_partition())
+            .entropy_weighted(|_| 0.)
+            .unwrap_err();
+        assert!(matches!(error, FittingError::Singular));
+    }
+
+    /// Points exactly on `H(n) = 2 - 3/n + 5/n^2 - 7/n^3`, over a wide range of sizes.
+    /// Solving the normal equations in `f32` misses the coefficients by up to `2e-2`.
+    #[test]
+    fn fit_polynomial_ill_conditioned() {
+        let naive_entropies: Vec<(usize, f64)> = (0..17)
+            .map(|i| {
+                let size = 1 << i;
+                let x = 1. / size as f64;
+                (size, 2. - 3. * x + 5. * x.powi(2) - 7. * x.powi(3))
+            })
+            .collect();
+        let coefficients: Vec<f32> = fit_polynomial(&naive_entropies, 3, |_| 1., 0.).unwrap();
+
+        let expected = [2., -3., 5., -7.];
+        let tolerances = [1e-5, 1e-3, 1e-3, 1e-2];
+        for i in 0..4 {
+            assert_float_eq!(coefficients[i], expected[i], abs <= tolerances[i]);
+        }
+    }
+
+    #[test_case(&[2, 2, 2], 2; "all_equal")]
+    #[test_case(&[3, 2, 2], 2; "two_distinct")]
+    #[test_case(&[2, 2], 1; "linear")]
+    fn degenerate_points(size_subsamples: &[usize], degree: usize) {
+        let samples = [0, 1, 0, 1, 1, 1, 0];
+        let samples_rep = vec![1; size_subsamples.len()];
+        let fixed = FixedPartition::new(&samples, size_subsamples, &samples_rep, degree).unwrap();
+        let mut estimator = Estimator::new(fixed);
+
+        let degenerate = |result: Result<f64, FittingError>| {
+            matches!(result, Err(FittingError::DegeneratePoints))
+        };
+        assert!(degenerate(estimator.entropy()));
+        assert!(degenerate(estimator.entropy_fit().map(|fit| fit.estimate)));
+        assert!(degenerate(estimator.entropy_weighted(|n| n as f64)));
+        assert!(degenerate(estimator.entropy_ridge(1.)));
+        assert!(degenerate(estimator.entropy_streaming()));
+        assert!(degenerate(
+            DirectEstimator::new(estimator.sampling_method().clone()).entropy()
+        ));
+    }
+
+    #[test]
+    fn entropy_clamped() {
+        let mut estimator = Estimator::new(negative_partition());
+        assert!(estimator.entropy().unwrap() < 0.);
+        assert_float_eq!(estimator.entropy_clamped().unwrap(), 0., abs <= 1e-12);
+
+        let fit = estimator.entropy_fit().unwrap();
+        assert!(fit.is_clamped());
+        assert_float_eq!(fit.clamped_estimate(), 0., abs <= 1e-12);
+    }
+
+    #[test]
+    fn entropy_clamped_positive() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = Estimator::new(bootstrap);
+        assert_float_eq!(
+            estimator.entropy_clamped().unwrap(),
+            1.8136715470699,
+            abs <= 1e-6
+        );
+    }
+
+    #[test]
+    fn entropy_ci() {
+        let width = |unnorm_distr: &[usize], replicates| {
+            let bootstrap = Bootstrap::new(unnorm_distr, 3, 2, rng(1)).unwrap();
+            let (lower, point, upper) = Estimator::new(bootstrap)
+                .entropy_ci(replicates, 0.1)
+                .unwrap();
+            assert!(lower <= point && point <= upper);
+            upper - lower
+        };
+        let small = width(&[1, 2, 3, 4, 5, 6], 20);
+        let large = width(&[100, 200, 300, 400, 500, 600], 200);
+
+        assert!(small > 0.);
+        assert!(large < small);
+    }
+
+    #[test]
+    fn entropy_ridge() {
+        let fixed = fixed_partition();
+        let naive_entropies = fixed.clone().naive_entropies();
+        let mean = naive_entropies.iter().map(|(_, value)| value).sum::<f64>()
+            / naive_entropies.len() as f64;
+        let mut estimator = Estimator::new(fixed);
+
+        let unregularized = estimator.entropy().unwrap();
+        let small = estimator.entropy_ridge(1e-12).unwrap();
+        assert_float_eq!(small, unregularized, abs <= 1e-6);
+
+        let weighted_mean = naive_entropies
+            .iter()
+            .map(|&(size, value)| (size * size) as f64 * value)
+            .sum::<f64>()
+            / naive_entropies
+                .iter()
+                .map(|&(size, _)| (size * size) as f64)
+                .sum::<f64>();
+        let large = estimator.entropy_ridge(1e12).unwrap();
+        assert_float_eq!(large, weighted_mean, abs <= 1e-6);
+        assert!((large - mean).abs() < (unregularized - mean).abs());
+    }
+
+    #[test]
+    fn fit_points() {
+        let naive_entropies = fixed_partition().naive_entropies();
+        let points = Estimator::new(fixed_partition()).fit_points();
+
+        assert_eq!(points.len(), naive_entropies.len());
+        for ((x, y), (size, value)) in points.iter().zip(&naive_entropies) {
+            assert_float_eq!(*x, 1. / *size as f64, abs <= 1e-12);
+            assert_float_eq!(*y, *value, abs <= 1e-12);
+        }
+        assert_eq!(DirectEstimator::new(fixed_partition()).fit_points(), points);
+    }
+
+    #[test]
+    fn entropy_ci_deterministic() {
+        let fixed = fixed_partition();
+        let (lower, point, upper) = Estimator::new(fixed).entropy_ci(10, 0.05).unwrap();
+
+        assert_float_eq!(lower, point, abs <= 1e-12);
+        assert_float_eq!(upper, point, abs <= 1e-12);
+    }
+
+    #[test]
+    fn extrapolation_distance() {
+        use rand::Rng;
+
+        let mut rng = rng(1);
+        let samples: Vec<usize> = (0..400).map(|_| rng.gen_range(0..10)).collect();
+        let big_subsamples = FixedPartition::new(&samples, &[200, 100, 50], &[1, 1, 2], 1).unwrap();
+        let small_subsamples = FixedPartition::new(&samples, &[50, 25, 12], &[2, 4, 8], 1).unwrap();
+
+        let big_distance = Estimator::new(big_subsamples)
+            .extrapolation_distance()
+            .unwrap();
+        let small_distance = Estimator::new(small_subsamples)
+            .extrapolation_distance()
+            .unwrap();
+        assert!(small_distance > big_distance);
+    }
+
+    #[test_case(&[1, 2, 3, 4, 5, 30], 5; "lowest_mse")]
+    #[test_case(&[1, 2, 4, 30], 4; "only_valid")]
+    fn optimal_num_groups(candidates: &[usize], expected: usize) {
+        use rand::Rng;
+
+        let mut rng = rng(1);
+        let samples: Vec<usize> = (0..1000).map(|_| rng.gen_range(0..10)).collect();
+        let unnorm_distr = count_dup_ordered(&samples);
+        let bootstrap = Bootstrap::new(&unnorm_distr, 3, 2, rng).unwrap();
+        let mut estimator = Estimator::new(bootstrap);
+
+        let num_groups = estimator.optimal_num_groups(candidates).unwrap();
+        assert_eq!(num_groups, expected);
+        assert_eq!(estimator.sampling_method().num_groups(), 3);
+    }
+
+    #[test]
+    fn optimal_num_groups_no_candidate() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = Estimator::new(bootstrap);
+
+        let error = estimator.optimal_num_groups(&[1, 2, 30]).unwrap_err();
+        assert!(matches!(error, FittingError::NoCandidate));
+        assert_eq!(estimator.sampling_method().num_groups(), 3);
+    }
+
+    #[test]
+    fn plug_in_entropy() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let estimator = Estimator::new(bootstrap);
+        assert_float_eq!(estimator.plug_in_entropy(), 1.66237699, abs <= 1e-6);
+    }
+
+    #[test]
+    fn entropy_normal_ci() {
+        let alpha = 0.05;
+        let width = |unnorm_distr: &[usize]| {
+            let bootstrap = Bootstrap::new(unnorm_distr, 3, 2, rng(1)).unwrap();
+            let (lower, upper) = Estimator::new(bootstrap).entropy_normal_ci(alpha).unwrap();
+            assert!(lower <= upper);
+            upper - lower
+        };
+
+        let small_width = width(&[1, 2, 3, 4, 5, 6]);
+        let big_width = width(&[10, 20, 30, 40, 50, 60]);
+        assert!(big_width < small_width);
+        assert_float_eq!(big_width * 10_f64.sqrt(), small_width, r2nd <= 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn entropy_normal_ci_invalid_alpha() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let _ = Estimator::new(bootstrap).entropy_normal_ci(1.5);
+    }
+
+    #[test]
+    fn saturation_size() {
+        let tol = 0.01;
+        let saturation_size = |num_symbols: usize| {
+            let samples: Vec<usize> = (0..120).map(|i| i % num_symbols).collect();
+            let fixed = FixedPartition::new(&samples, &[40, 20, 10], &[1, 2, 4], 2).unwrap();
+            Estimator::new(fixed).saturation_size(tol).unwrap()
+        };
+
+        assert_eq!(saturation_size(1), 1);
+        assert!(saturation_size(2) < saturation_size(8));
+    }
+
+    #[test_case(f64::NAN; "nan")]
+    #[test_case(f64::INFINITY; "infinite")]
+    fn saturation_size_not_finite(outlier: f64) {
+        let mut estimator = Estimator::new(Linear {
+            size_subsamples: vec![64, 32, 16, 4],
+            samples_rep: vec![1; 4],
+            noise: 0.,
+            outlier,
+        });
+
+        assert!(estimator.coefficients().is_ok());
+        assert_eq!(estimator.saturation_size(0.01), None);
+    }
+
+    #[test]
+    fn expected_entropy() {
+        let variance = |values: &[f64]| {
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / values.len() as f64
+        };
+        let estimator = |seed| {
+            let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(seed)).unwrap();
+            Estimator::new(bootstrap)
+        };
+
+        let single: Vec<f64> = (0..20)
+            .map(|seed| estimator(seed).entropy().unwrap())
+            .collect();
+        let smoothed: Vec<f64> = (0..20)
+            .map(|seed| estimator(seed).expected_entropy(10).unwrap())
+            .collect();
+        assert!(variance(&smoothed) < variance(&single));
+    }
+
+    #[test]
+    fn seed_sensitivity() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rng(1);
+        let samples: Vec<usize> = (0..1000).map(|_| rng.gen_range(0..50)).collect();
+        let unnorm_distr = crate::count_dup(&samples);
+        let seeds: Vec<u64> = (0..20).collect();
+        let std = |num_groups| {
+            let rng = rand_pcg::Pcg32::seed_from_u64(0);
+            let bootstrap = Bootstrap::new(&unnorm_distr, num_groups, 1, rng).unwrap();
+            let sensitivity = Estimator::new(bootstrap).seed_sensitivity(&seeds).unwrap();
+            assert!(sensitivity.range >= sensitivity.std);
+            sensitivity.std
+        };
+
+        assert!(std(5) < std(2));
+    }
+
+    #[test]
+    fn entropy_weighted_constant() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let expected = Estimator::new(bootstrap).entropy().unwrap();
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let value = Estimator::new(bootstrap).entropy_weighted(|_| 3.).unwrap();
+
+        assert_float_eq!(value, expected, abs <= 1e-9);
+    }
+
+    /// Weights favouring large subsamples follow their naive entropies
+    #[test]
+    fn entropy_weighted() {
+        let outlier = || Linear {
+            size_subsamples: vec![64, 32, 16, 4],
+            samples_rep: vec![1; 4],
+            noise: 0.,
+            outlier: 0.5,
+        };
+        let unweighted = Estimator::new(outlier()).entropy().unwrap();
+        let weighted = Estimator::new(outlier())
+            .entropy_weighted(|n| (n as f64).powi(4))
+            .unwrap();
+
+        assert!((unweighted - 2.).abs() > 0.01);
+        assert_float_eq!(weighted, 2., abs <= 1e-3);
+    }
+
+    #[test]
+    fn entropy_with_base() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = Estimator::new(bootstrap);
+        let expected = 1.8136715470699 / 2_f64.ln();
+
+        assert_float_eq!(
+            estimator.entropy_with_base(LogBase::Bits).unwrap(),
+            expected,
+            abs <= 1e-6
+        );
+    }
+
+    #[test]
+    fn perplexity() {
+        let mut estimator = Estimator::from_seed(&[200; 8], 1);
+        assert_float_eq!(estimator.perplexity().unwrap(), 8., abs <= 1e-1);
+
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = Estimator::new(bootstrap);
+        assert_float_eq!(
+            estimator.perplexity().unwrap(),
+            1.8136715470699_f64.exp(),
+            abs <= 1e-6
+        );
+    }
+
+    #[test]
+    fn normalized_entropy() {
+        let mut estimator = Estimator::from_seed(&[200; 8], 1);
+        assert_float_eq!(estimator.normalized_entropy(8).unwrap(), 1., abs <= 1e-2);
+        assert_eq!(estimator.normalized_entropy(1).unwrap(), 0.);
+    }
+
+    #[test]
+    fn entropy_fit() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = Estimator::new(bootstrap);
+        let fit = estimator.entropy_fit().unwrap();
+
+        assert_float_eq!(fit.estimate, 1.8136715470699, abs <= 1e-6);
+        assert_eq!(fit.coefficients.len(), 3);
+        assert_float_eq!(fit.coefficients[0], fit.estimate, abs <= 1e-12);
+        assert_eq!(fit.naive_entropies.len(), 21);
+    }
+
+    #[test]
+    fn from_seed() {
+        let unnorm_distr = [1, 2, 3, 4, 5, 6];
+        let first = Estimator::from_seed(&unnorm_distr, 7).entropy().unwrap();
+        let second = Estimator::from_seed(&unnorm_distr, 7).entropy().unwrap();
+        let other = Estimator::from_seed(&unnorm_distr, 8).entropy().unwrap();
+
+        assert_float_eq!(first, second, abs <= 1e-12);
+        assert!(first != other);
+    }
 }
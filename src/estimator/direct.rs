@@ -1,12 +1,11 @@
-use core::hash::Hash;
+use core::{fmt, hash::Hash};
 use polyfit_rs::polyfit_rs::polyfit;
-use rand::rngs::ThreadRng;
+use rand::{rngs::ThreadRng, SeedableRng};
+use rand_pcg::Pcg32;
 use thiserror::Error;
 
-use crate::{Bootstrap, SamplingMethod};
-
-const DEFAULT_NUM_GROUPS: usize = 3;
-const DEFAULT_DEGREE: usize = 2;
+use super::{check_fit, EntropyEstimate, FittingError, DEFAULT_DEGREE, DEFAULT_NUM_GROUPS};
+use crate::{Bootstrap, LogBase, SamplingMethod};
 
 /// Direct entropy estimator.
 ///
@@ -40,9 +39,37 @@ pub struct DirectEstimator<M> {
     sampling_method: M,
 }
 
+/// Polynomial fit of naive entropy estimations, with the points used.
+struct Fit {
+    coefficients: Vec<f64>,
+    inverse_sizes: Vec<f64>,
+    naive_entropy_values: Vec<f64>,
+}
+
+/// Quality of the polynomial fit of naive entropy estimations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FitDiagnostics {
+    /// Coefficient of determination of the fit.
+    ///
+    /// It is one if the naive entropy estimations are all equal.
+    pub r_squared: f64,
+    /// Sum of the squared differences between naive entropy estimations
+    /// and the fitted polynomial.
+    pub residual_sum_squares: f64,
+    /// Number of naive entropy estimations used for the fit.
+    pub num_points: usize,
+}
+
 #[derive(Error, Debug)]
-#[error("Failed to estimate entropy because of numerical instability.")]
-pub struct FittingError;
+pub enum PredictionError<E>
+where
+    E: std::error::Error + 'static,
+{
+    #[error("Failed to predict entropy because of an invalid unnormalized distribution.")]
+    InvalidUnnormDistr(#[source] E),
+    #[error(transparent)]
+    Fitting(#[from] FittingError),
+}
 
 /// # Basic methods
 impl<M> DirectEstimator<M>
@@ -60,26 +87,235 @@ where
     /// Estimates the entropy of the underlying distribution,
     /// known only through the empirical unnormalized distribution.
     ///
+    /// # Remarks
+    ///
+    /// If a single element was observed, zero is returned exactly, without fitting.
+    ///
     /// # Errors
     ///
-    /// If there are numerical instabilities.
+    /// If there are fewer distinct subsample sizes than the degree plus one
+    /// (see [`can_fit`]), or if there are numerical instabilities.
+    ///
+    /// [`can_fit`]: trait.SamplingMethod.html#method.can_fit
     pub fn entropy(&mut self) -> Result<f64, FittingError> {
-        let (inverse_size_subsamples_dup, naive_entropy_values): (Vec<_>, Vec<_>) = self
-            .sampling_method
+        if let Some(entropy) = check_fit(&self.sampling_method, self.sampling_method.degree())? {
+            return Ok(entropy);
+        }
+        let coefficients = self.coefficients()?;
+        Ok(coefficients[0])
+    }
+
+    /// Estimates the entropy, expressed in the logarithm base `base`.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities.
+    pub fn entropy_with_base(&mut self, base: LogBase) -> Result<f64, FittingError> {
+        Ok(base.from_nats(self.entropy()?))
+    }
+
+    /// Returns the fitted polynomial, as a function of `1/n`,
+    /// where `n` is the size of a subsample.
+    ///
+    /// Evaluating it at `0` gives the same value as [`entropy`].
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::DirectEstimator;
+    /// let unnorm_distr = [1, 2, 3, 4, 5, 6];
+    /// let mut estimator = DirectEstimator::from(unnorm_distr);
+    /// let polynomial = estimator.fitted_polynomial().unwrap();
+    /// println!("Entropy estimation: {:?}", polynomial(0.)); // Random result
+    /// ```
+    ///
+    /// [`entropy`]: #method.entropy
+    pub fn fitted_polynomial(&mut self) -> Result<impl Fn(f64) -> f64, FittingError> {
+        let coefficients = self.coefficients()?;
+        Ok(move |x: f64| evaluate(&coefficients, x))
+    }
+
+    /// Estimates the entropy as if the unnormalized distribution was `unnorm_distr`,
+    /// keeping the rest of the configuration.
+    ///
+    /// This is useful to compare hypothetical scenarios.
+    /// The estimation is done on a copy of the sampling method,
+    /// so the estimator (including its random number generator) is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the sampling method rejects `unnorm_distr`,
+    /// or if there are numerical instabilities.
+    pub fn predict_entropy_for(
+        &self,
+        unnorm_distr: &[M::Count],
+    ) -> Result<f64, PredictionError<M::UnnormDistrError>>
+    where
+        M: Clone,
+    {
+        let mut sampling_method = self.sampling_method.clone();
+        sampling_method
+            .set_unnorm_distr(unnorm_distr)
+            .map_err(PredictionError::InvalidUnnormDistr)?;
+        Ok(DirectEstimator::new(sampling_method).entropy()?)
+    }
+
+    /// Estimates the entropy and returns diagnostics of the quality of the fit.
+    ///
+    /// See [`FitDiagnostics`] for the details.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities.
+    ///
+    /// [`FitDiagnostics`]: struct.FitDiagnostics.html
+    pub fn entropy_with_diagnostics(&mut self) -> Result<(f64, FitDiagnostics), FittingError> {
+        let Fit {
+            coefficients,
+            inverse_sizes,
+            naive_entropy_values,
+        } = self.fit()?;
+
+        let mean = naive_entropy_values.iter().sum::<f64>() / naive_entropy_values.len() as f64;
+        let total_sum_squares: f64 = naive_entropy_values
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum();
+        let residual_sum_squares: f64 = inverse_sizes
+            .iter()
+            .zip(&naive_entropy_values)
+            .map(|(x, value)| (value - evaluate(&coefficients, *x)).powi(2))
+            .sum();
+        let r_squared = if total_sum_squares > 0. {
+            1. - residual_sum_squares / total_sum_squares
+        } else {
+            1.
+        };
+
+        let diagnostics = FitDiagnostics {
+            r_squared,
+            residual_sum_squares,
+            num_points: naive_entropy_values.len(),
+        };
+        Ok((coefficients[0], diagnostics))
+    }
+
+    /// Estimates the entropy with the degree that best predicts the naive entropy estimations,
+    /// and returns the estimation together with the chosen degree.
+    ///
+    /// Each degree from `1` to `max_degree` (and smaller than the number of groups)
+    /// is scored by leave-one-out cross-validation: every naive entropy estimation is predicted
+    /// by a fit of all the others, and squared prediction errors are added up.
+    /// The degree with the smallest error wins, the smallest one in case of ties.
+    ///
+    /// # Remarks
+    ///
+    /// The same naive entropy estimations are used for all degrees.
+    /// The degree of the sampling method is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If there is no valid degree (`max_degree` is zero, or there is only one group),
+    /// or if there are numerical instabilities.
+    pub fn entropy_auto_degree(&mut self, max_degree: usize) -> Result<(f64, usize), FittingError> {
+        let (inverse_sizes, naive_entropy_values) = self.points();
+        let max_degree = max_degree.min(self.sampling_method.num_groups().saturating_sub(1));
+
+        let mut best: Option<(f64, usize)> = None;
+        for degree in 1..=max_degree {
+            let error = match leave_one_out_error(&inverse_sizes, &naive_entropy_values, degree) {
+                Some(error) => error,
+                None => continue,
+            };
+            match best {
+                Some((best_error, _)) if best_error <= error => {}
+                _ => best = Some((error, degree)),
+            }
+        }
+        let (_, degree) = best.ok_or(FittingError::DegeneratePoints)?;
+
+        let coefficients = polyfit(&inverse_sizes, &naive_entropy_values, degree)
+            .map_err(|error| FittingError::PolyFit(error.to_string()))?;
+        Ok((coefficients[0], degree))
+    }
+
+    /// Same as [`Estimator::fit_points`].
+    ///
+    /// [`Estimator::fit_points`]: struct.Estimator.html#method.fit_points
+    pub fn fit_points(&mut self) -> Vec<(f64, f64)> {
+        self.sampling_method
             .naive_entropies()
             .into_iter()
-            .map(|(size, value)| ((1. / size as f64), value))
-            .unzip();
+            .map(|(size, value)| (1. / size as f64, value))
+            .collect()
+    }
+
+    /// Fits a polynomial in `1/n` to the naive entropy estimations
+    /// and returns its coefficients, from the constant term upwards.
+    fn coefficients(&mut self) -> Result<Vec<f64>, FittingError> {
+        Ok(self.fit()?.coefficients)
+    }
+
+    /// Fits a polynomial in `1/n` to the naive entropy estimations
+    /// and returns its coefficients, together with the points used for the fit.
+    fn fit(&mut self) -> Result<Fit, FittingError> {
+        check_fit(&self.sampling_method, self.sampling_method.degree())?;
+        let (inverse_size_subsamples_dup, naive_entropy_values) = self.points();
 
         // Fitting a polynomial
-        match polyfit(
+        let coefficients = polyfit(
             &inverse_size_subsamples_dup,
             &naive_entropy_values,
             self.sampling_method().degree(),
-        ) {
-            Ok(coefficients) => Ok(coefficients[0]),
-            Err(_) => Err(FittingError),
-        }
+        )
+        .map_err(|error| FittingError::PolyFit(error.to_string()))?;
+        Ok(Fit {
+            coefficients,
+            inverse_sizes: inverse_size_subsamples_dup,
+            naive_entropy_values,
+        })
+    }
+
+    /// Computes naive entropy estimations, as the inverse sizes of the subsamples
+    /// and the corresponding values.
+    fn points(&mut self) -> (Vec<f64>, Vec<f64>) {
+        self.fit_points().into_iter().unzip()
+    }
+}
+
+/// Evaluates the polynomial with `coefficients`, from the constant term upwards, at `x`.
+fn evaluate(coefficients: &[f64], x: f64) -> f64 {
+    // Horner's method
+    coefficients
+        .iter()
+        .rev()
+        .fold(0., |acc, coefficient| acc * x + coefficient)
+}
+
+/// Sum of the squared errors when predicting each point by a fit of all the others.
+///
+/// Returns `None` if a fit fails or the error is not finite.
+fn leave_one_out_error(xs: &[f64], ys: &[f64], degree: usize) -> Option<f64> {
+    let mut error = 0.;
+    for i in 0..xs.len() {
+        let (other_xs, other_ys): (Vec<f64>, Vec<f64>) = xs
+            .iter()
+            .zip(ys)
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, (x, y))| (*x, *y))
+            .unzip();
+        let coefficients = polyfit(&other_xs, &other_ys, degree).ok()?;
+        error += (ys[i] - evaluate(&coefficients, xs[i])).powi(2);
+    }
+    if error.is_finite() {
+        Some(error)
+    } else {
+        None
     }
 }
 
@@ -113,6 +349,25 @@ impl<M> DirectEstimator<M> {
     }
 }
 
+impl<M> EntropyEstimate for DirectEstimator<M>
+where
+    M: SamplingMethod,
+{
+    fn entropy(&mut self) -> Result<f64, FittingError> {
+        DirectEstimator::entropy(self)
+    }
+}
+
+/// Summary of the configuration of the sampling method.
+impl<M> fmt::Display for DirectEstimator<M>
+where
+    M: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DirectEstimator({})", self.sampling_method)
+    }
+}
+
 impl<M> From<M> for DirectEstimator<M>
 where
     M: SamplingMethod,
@@ -122,6 +377,43 @@ where
     }
 }
 
+/// # Reproducible constructors
+impl DirectEstimator<Bootstrap<Pcg32>> {
+    /// Constructs a new `DirectEstimator` from an unnormalized distribution,
+    /// using a random number generator seeded with `seed`.
+    ///
+    /// For a fixed unnormalized distribution, the seed fully determines
+    /// the estimation (up to floating point rounding).
+    ///
+    /// # Remarks
+    ///
+    /// Default values are given to tunable parameters, as in `From<[usize; N]>`.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples for the default number of groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::DirectEstimator;
+    /// let unnorm_distr = [1, 2, 3, 4, 5, 6];
+    /// let first = DirectEstimator::from_seed(&unnorm_distr, 1).entropy().unwrap();
+    /// let second = DirectEstimator::from_seed(&unnorm_distr, 1).entropy().unwrap();
+    /// assert!((first - second).abs() < 1e-12);
+    /// ```
+    pub fn from_seed(unnorm_distr: &[usize], seed: u64) -> Self {
+        let sampling_method = Bootstrap::new(
+            unnorm_distr,
+            DEFAULT_NUM_GROUPS,
+            DEFAULT_DEGREE,
+            Pcg32::seed_from_u64(seed),
+        )
+        .unwrap();
+        DirectEstimator::new(sampling_method)
+    }
+}
+
 impl<const N: usize> From<[usize; N]> for DirectEstimator<Bootstrap<ThreadRng>> {
     /// Performs the conversion from an unnormalized distribution.
     ///
@@ -189,7 +481,10 @@ mod tests {
     use float_eq::assert_float_eq;
     use test_case::test_case;
 
-    use crate::test::rng;
+    use crate::{
+        test::{fixed_partition, rng, Linear},
+        FixedPartition,
+    };
 
     #[test_case([8]; "one_sample")]
     #[test_case([1, 2, 3, 4, 5, 6]; "[usize; N]")]
@@ -203,8 +498,8 @@ mod tests {
     }
 
     /// Value stability of implementation
-    #[test_case([1, 2, 3, 4, 5, 6], 1.9511041580553; "increasing")]
-    #[test_case(vec!['a', 'b', 'c', 'd', 'd', 'e', 'e', 'e'], 1.9511041580553; "letters")]
+    #[test_case([1, 2, 3, 4, 5, 6], 1.8136715470699; "increasing")]
+    #[test_case(vec!['a', 'b', 'c', 'd', 'd', 'e', 'e', 'e'], 1.8136715470699; "letters")]
     fn entropy<T>(source: T, expected: f64)
     where
         DirectEstimator<Bootstrap<ThreadRng>>: From<T>,
@@ -217,4 +512,120 @@ mod tests {
 
         assert_float_eq!(estimator.entropy().unwrap(), expected, abs <= 1e-6);
     }
+
+    #[test]
+    fn fitted_polynomial() {
+        let mut estimator = DirectEstimator::new(fixed_partition());
+
+        let coefficients = estimator.coefficients().unwrap();
+        let polynomial = estimator.fitted_polynomial().unwrap();
+
+        assert_float_eq!(polynomial(0.), estimator.entropy().unwrap(), abs <= 1e-6);
+        for x in [0.1, 0.25, 0.5, 1.] {
+            let expected = coefficients[0] + coefficients[1] * x + coefficients[2] * x * x;
+            assert_float_eq!(polynomial(x), expected, abs <= 1e-6);
+        }
+    }
+
+    #[test]
+    fn predict_entropy_for() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = DirectEstimator::new(bootstrap);
+
+        let prediction = estimator.predict_entropy_for(&[10, 10, 10, 10]).unwrap();
+        assert!(prediction.is_finite());
+        assert!(estimator.predict_entropy_for(&[1]).is_err());
+
+        assert_eq!(
+            estimator.sampling_method().pooled_unnorm_distr(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+        assert_float_eq!(estimator.entropy().unwrap(), 1.8136715470699, abs <= 1e-6);
+    }
+
+    #[test]
+    fn entropy_with_base() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = DirectEstimator::new(bootstrap);
+        let expected = 1.8136715470699 / 2_f64.ln();
+
+        assert_float_eq!(
+            estimator.entropy_with_base(LogBase::Bits).unwrap(),
+            expected,
+            abs <= 1e-6
+        );
+    }
+
+    fn linear(noise: f64) -> Linear {
+        Linear {
+            size_subsamples: vec![40, 20, 10],
+            samples_rep: vec![1, 2, 4],
+            noise,
+            outlier: 0.,
+        }
+    }
+
+    #[test]
+    fn entropy_with_diagnostics() {
+        let mut estimator = DirectEstimator::new(linear(0.));
+        let (entropy, diagnostics) = estimator.entropy_with_diagnostics().unwrap();
+
+        assert_float_eq!(entropy, 2., abs <= 1e-9);
+        assert_float_eq!(diagnostics.r_squared, 1., abs <= 1e-9);
+        assert_float_eq!(diagnostics.residual_sum_squares, 0., abs <= 1e-9);
+        assert_eq!(diagnostics.num_points, 7);
+    }
+
+    #[test_case(0.; "exact")]
+    #[test_case(0.01; "noisy")]
+    fn entropy_auto_degree(noise: f64) {
+        let mut estimator = DirectEstimator::new(linear(noise));
+        let (entropy, degree) = estimator.entropy_auto_degree(5).unwrap();
+
+        assert_eq!(degree, 1);
+        assert_float_eq!(entropy, 2., abs <= 0.05);
+    }
+
+    #[test]
+    fn entropy_auto_degree_invalid() {
+        let mut estimator = DirectEstimator::new(linear(0.));
+        assert!(estimator.entropy_auto_degree(0).is_err());
+    }
+
+    #[test]
+    fn from_seed() {
+        let unnorm_distr = [1, 2, 3, 4, 5, 6];
+        let first = DirectEstimator::from_seed(&unnorm_distr, 7)
+            .entropy()
+            .unwrap();
+        let second = DirectEstimator::from_seed(&unnorm_distr, 7)
+            .entropy()
+            .unwrap();
+        let other = DirectEstimator::from_seed(&unnorm_distr, 8)
+            .entropy()
+            .unwrap();
+
+        assert_float_eq!(first, second, abs <= 1e-12);
+        assert!(first != other);
+    }
+
+    #[test]
+    fn entropy_single_element() {
+        for seed in 0..10 {
+            let value = DirectEstimator::from_seed(&[8], seed).entropy().unwrap();
+            assert_eq!(value, 0.);
+        }
+    }
+
+    #[test]
+    fn display() {
+        let samples = [0, 0, 0, 1, 1, 2];
+        let fixed = FixedPartition::new(&samples, &[3, 2, 1], &[1, 1, 1], 2).unwrap();
+        let estimator = DirectEstimator::new(fixed);
+
+        assert_eq!(
+            estimator.to_string(),
+            format!("DirectEstimator({})", estimator.sampling_method())
+        );
+    }
 }
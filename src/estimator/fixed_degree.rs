@@ -0,0 +1,136 @@
+use nalgebra::{
+    allocator::Allocator, Const, DefaultAllocator, DimNameAdd, DimNameSum, OMatrix, OVector, U1,
+};
+
+use super::{check_fit, fold_normal_equations, EntropyEstimate, FittingError};
+use crate::SamplingMethod;
+
+/// Entropy estimator whose polynomial has a degree `D` known at compile time.
+///
+/// It extrapolates naive entropy estimations like [`Estimator::entropy_streaming`],
+/// but the `(D + 1) × (D + 1)` normal equations are stored and solved on the stack.
+///
+/// # Remarks
+///
+/// The degree of the sampling method is ignored, `D` is used instead.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::{Bootstrap, FixedDegreeEstimator};
+/// let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rand::thread_rng()).unwrap();
+/// let mut estimator = FixedDegreeEstimator::<_, 2>::new(bootstrap);
+/// println!("Entropy estimation: {:?}", estimator.entropy()); // Random result
+/// ```
+///
+/// [`Estimator::entropy_streaming`]: struct.Estimator.html#method.entropy_streaming
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedDegreeEstimator<M, const D: usize> {
+    sampling_method: M,
+}
+
+impl<M, const D: usize> FixedDegreeEstimator<M, D>
+where
+    M: SamplingMethod,
+    Const<D>: DimNameAdd<U1>,
+    DefaultAllocator: Allocator<f64, DimNameSum<Const<D>, U1>>
+        + Allocator<f64, DimNameSum<Const<D>, U1>, DimNameSum<Const<D>, U1>>,
+{
+    pub fn new(sampling_method: M) -> Self {
+        FixedDegreeEstimator { sampling_method }
+    }
+
+    /// Returns the underlying sampling method.
+    pub fn sampling_method(&self) -> &M {
+        &self.sampling_method
+    }
+
+    /// Returns the underlying sampling method.
+    pub fn sampling_method_mut(&mut self) -> &mut M {
+        &mut self.sampling_method
+    }
+
+    /// Estimates the entropy of the underlying distribution,
+    /// known only through the empirical unnormalized distribution.
+    ///
+    /// # Remarks
+    ///
+    /// If a single element was observed, zero is returned exactly, without fitting.
+    ///
+    /// # Errors
+    ///
+    /// If there are fewer distinct subsample sizes than `D + 1`,
+    /// or if there are numerical instabilities.
+    pub fn entropy(&mut self) -> Result<f64, FittingError> {
+        if let Some(entropy) = check_fit(&self.sampling_method, D)? {
+            return Ok(entropy);
+        }
+        let mut gram = OMatrix::<f64, DimNameSum<Const<D>, U1>, DimNameSum<Const<D>, U1>>::zeros();
+        let mut rhs = OVector::<f64, DimNameSum<Const<D>, U1>>::zeros();
+        let mut row = OVector::<f64, DimNameSum<Const<D>, U1>>::zeros();
+        fold_normal_equations(
+            self.sampling_method.naive_entropies_iter(),
+            &mut row,
+            |row, y| {
+                gram.ger(1., row, row, 1.);
+                rhs.axpy(y, row, 1.);
+            },
+        )?;
+
+        // Columns `n^{1 - k}` have very different magnitudes, so the system is equilibrated
+        let scales = gram.diagonal().map(|entry| 1. / entry.sqrt());
+        let scaled_gram =
+            OMatrix::<f64, DimNameSum<Const<D>, U1>, DimNameSum<Const<D>, U1>>::from_fn(|i, j| {
+                gram[(i, j)] * scales[i] * scales[j]
+            });
+        let scaled_rhs = rhs.component_mul(&scales);
+        let solution = scaled_gram
+            .cholesky()
+            .ok_or(FittingError::Singular)?
+            .solve(&scaled_rhs);
+        Ok(solution[0] * scales[0])
+    }
+}
+
+impl<M, const D: usize> EntropyEstimate for FixedDegreeEstimator<M, D>
+where
+    M: SamplingMethod,
+    Const<D>: DimNameAdd<U1>,
+    DefaultAllocator: Allocator<f64, DimNameSum<Const<D>, U1>>
+        + Allocator<f64, DimNameSum<Const<D>, U1>, DimNameSum<Const<D>, U1>>,
+{
+    fn entropy(&mut self) -> Result<f64, FittingError> {
+        FixedDegreeEstimator::entropy(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    use crate::{test::rng, Bootstrap, Estimator};
+
+    #[test]
+    fn matches_estimator() {
+        let unnorm_distr = [1, 2, 3, 4, 5, 6];
+        let bootstrap = Bootstrap::new(&unnorm_distr, 3, 2, rng(1)).unwrap();
+        let expected = Estimator::new(bootstrap).entropy().unwrap();
+        let bootstrap = Bootstrap::new(&unnorm_distr, 3, 2, rng(1)).unwrap();
+        let value = FixedDegreeEstimator::<_, 2>::new(bootstrap)
+            .entropy()
+            .unwrap();
+
+        assert_float_eq!(value, expected, abs <= 1e-9);
+    }
+
+    #[test]
+    fn degenerate_points() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let error = FixedDegreeEstimator::<_, 3>::new(bootstrap)
+            .entropy()
+            .unwrap_err();
+
+        assert!(matches!(error, FittingError::DegeneratePoints));
+    }
+}
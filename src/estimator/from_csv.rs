@@ -0,0 +1,133 @@
+use rand::SeedableRng;
+use rand_pcg::Pcg32;
+use std::io::Read;
+use thiserror::Error;
+
+use super::{DEFAULT_DEGREE, DEFAULT_NUM_GROUPS};
+use crate::{utils::count_dup, Bootstrap, Estimator};
+
+#[derive(Error, Debug)]
+pub enum ReadError {
+    #[error("Failed to read the samples: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("Failed to read the samples: row {row} has no column {column}.")]
+    MissingColumn { row: usize, column: usize },
+    #[error("Failed construction. There are too few samples for the default number of groups.")]
+    TooFewSamples,
+}
+
+/// # Reading samples
+impl Estimator<Bootstrap<Pcg32>> {
+    /// Constructs a new `Estimator` from the samples in column `column` (starting at zero)
+    /// of comma separated values, using a random number generator seeded with `seed`.
+    /// If `has_header` is `true`, the first row is a header and is skipped.
+    ///
+    /// Samples are compared as text (after trimming whitespace),
+    /// so they can be integers or strings.
+    /// A file with a single column is just one sample per line.
+    ///
+    /// # Remarks
+    ///
+    /// Default values are given to tunable parameters, as in [`from_seed`].
+    ///
+    /// # Errors
+    ///
+    /// If the input can not be read, if rows have different numbers of fields,
+    /// if a row has no column `column`,
+    /// or if there are too few samples for the default number of groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::Estimator;
+    /// let csv = "id,color\n1,red\n2,blue\n3,red\n4,green\n5,red\n6,blue\n7,red\n8,red\n";
+    /// let mut estimator = Estimator::from_csv_column(csv.as_bytes(), 1, true, 1).unwrap();
+    /// println!("Entropy estimation: {:?}", estimator.entropy());
+    /// ```
+    ///
+    /// [`from_seed`]: #method.from_seed
+    pub fn from_csv_column<R>(
+        reader: R,
+        column: usize,
+        has_header: bool,
+        seed: u64,
+    ) -> Result<Self, ReadError>
+    where
+        R: Read,
+    {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .has_headers(has_header)
+            .from_reader(reader);
+
+        let mut samples = Vec::new();
+        for (row, record) in csv_reader.records().enumerate() {
+            let record = record?;
+            let value = record
+                .get(column)
+                .ok_or(ReadError::MissingColumn { row, column })?;
+            samples.push(value.trim().to_string());
+        }
+
+        let unnorm_distr = count_dup(&samples);
+        let sampling_method = Bootstrap::new(
+            &unnorm_distr,
+            DEFAULT_NUM_GROUPS,
+            DEFAULT_DEGREE,
+            Pcg32::seed_from_u64(seed),
+        )
+        .map_err(|_| ReadError::TooFewSamples)?;
+        Ok(Estimator::new(sampling_method))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    use crate::SamplingMethod;
+
+    #[test]
+    fn from_csv_column() {
+        let csv = "id,value\n1,3\n2,1\n3,3\n4,2\n5,3\n6,1\n7,3\n8,2\n9,3\n";
+        let mut estimator = Estimator::from_csv_column(Cursor::new(csv), 1, true, 1).unwrap();
+        let mut pooled = estimator.sampling_method().pooled_unnorm_distr();
+        pooled.sort_unstable();
+        assert_eq!(pooled, vec![2, 2, 5]);
+
+        assert!(estimator.entropy().unwrap().is_finite());
+    }
+
+    #[test]
+    fn strings_with_header() {
+        let csv = "id,color\n1,red\n2,blue\n3,red\n4,green\n5,red\n6,blue\n7,red\n8,green\n";
+        let estimator = Estimator::from_csv_column(Cursor::new(csv), 1, true, 1).unwrap();
+        let mut pooled = estimator.sampling_method().pooled_unnorm_distr();
+        pooled.sort_unstable();
+        assert_eq!(pooled, vec![2, 2, 4]);
+    }
+
+    #[test]
+    fn strings_without_header() {
+        let csv = "a\nb\na\nc\na\nb\na\nd\n";
+        let estimator = Estimator::from_csv_column(Cursor::new(csv), 0, false, 1).unwrap();
+        assert_eq!(estimator.sampling_method().pooled_unnorm_distr().len(), 4);
+    }
+
+    #[test]
+    fn malformed() {
+        let csv = "1,2\n3\n4,5\n";
+        let result = Estimator::from_csv_column(Cursor::new(csv), 0, false, 1);
+        assert!(matches!(result, Err(ReadError::Csv(_))));
+    }
+
+    #[test]
+    fn missing_column() {
+        let csv = "1,2\n3,4\n";
+        let result = Estimator::from_csv_column(Cursor::new(csv), 2, false, 1);
+        assert!(matches!(
+            result,
+            Err(ReadError::MissingColumn { row: 0, column: 2 })
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+use core::hash::Hash;
+#[cfg(not(feature = "std"))]
+use hashbrown::HashMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::NaiveEstimator;
+
+/// Naive joint, marginal and conditional entropies of paired samples.
+///
+/// The counts of `X`, `Y` and `(X, Y)` are computed once, in a single pass,
+/// so that all quantities can be queried without scanning the samples again.
+///
+/// # Remarks
+///
+/// An empty sample has zero entropy.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::JointAnalyzer;
+/// let samples = [(0, 'a'), (0, 'a'), (1, 'b'), (1, 'c')];
+/// let analyzer = JointAnalyzer::new(&samples);
+/// println!("Mutual information: {:?}", analyzer.mutual_info());
+/// ```
+#[derive(Debug, Clone)]
+pub struct JointAnalyzer<X, Y> {
+    x_counts: HashMap<X, usize>,
+    y_counts: HashMap<Y, usize>,
+    xy_counts: HashMap<(X, Y), usize>,
+}
+
+impl<X, Y> JointAnalyzer<X, Y>
+where
+    X: Hash + Eq + Clone,
+    Y: Hash + Eq + Clone,
+{
+    /// Constructs a new `JointAnalyzer` from paired samples.
+    pub fn new(samples: &[(X, Y)]) -> Self {
+        let mut x_counts = HashMap::new();
+        let mut y_counts = HashMap::new();
+        let mut xy_counts = HashMap::new();
+        for (x, y) in samples {
+            *x_counts.entry(x.clone()).or_insert(0) += 1;
+            *y_counts.entry(y.clone()).or_insert(0) += 1;
+            *xy_counts.entry((x.clone(), y.clone())).or_insert(0) += 1;
+        }
+
+        JointAnalyzer {
+            x_counts,
+            y_counts,
+            xy_counts,
+        }
+    }
+
+    /// Returns the naive entropy of `X`.
+    pub fn h_x(&self) -> f64 {
+        naive_entropy(self.x_counts.values())
+    }
+
+    /// Returns the naive entropy of `Y`.
+    pub fn h_y(&self) -> f64 {
+        naive_entropy(self.y_counts.values())
+    }
+
+    /// Returns the naive joint entropy of `(X, Y)`.
+    pub fn h_xy(&self) -> f64 {
+        naive_entropy(self.xy_counts.values())
+    }
+
+    /// Returns the naive conditional entropy of `X` given `Y`,
+    /// `H(X | Y) = H(X, Y) - H(Y)`.
+    pub fn h_x_given_y(&self) -> f64 {
+        self.h_xy() - self.h_y()
+    }
+
+    /// Returns the naive mutual information between `X` and `Y`,
+    /// `I(X; Y) = H(X) + H(Y) - H(X, Y)`.
+    pub fn mutual_info(&self) -> f64 {
+        self.h_x() + self.h_y() - self.h_xy()
+    }
+}
+
+fn naive_entropy<'a, I>(counts: I) -> f64
+where
+    I: Iterator<Item = &'a usize>,
+{
+    let unnorm_distr: Vec<usize> = counts.copied().collect();
+    match NaiveEstimator::new(&unnorm_distr) {
+        Ok(naive_estimator) => naive_estimator.entropy(),
+        Err(_) => 0.,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn identity() {
+        let samples: Vec<(usize, usize)> = [0, 1, 1, 2, 2, 2, 3].iter().map(|&x| (x, x)).collect();
+        let analyzer = JointAnalyzer::new(&samples);
+        let expected = NaiveEstimator::new(&[1, 2, 3, 1]).unwrap().entropy();
+
+        assert_float_eq!(analyzer.h_x(), expected, abs <= 1e-12);
+        assert_float_eq!(analyzer.h_y(), expected, abs <= 1e-12);
+        assert_float_eq!(analyzer.h_xy(), expected, abs <= 1e-12);
+        assert_float_eq!(analyzer.h_x_given_y(), 0., abs <= 1e-12);
+        assert_float_eq!(analyzer.mutual_info(), expected, abs <= 1e-12);
+    }
+
+    #[test]
+    fn independent() {
+        let samples = [(0, 'a'), (0, 'b'), (1, 'a'), (1, 'b')];
+        let analyzer = JointAnalyzer::new(&samples);
+
+        assert_float_eq!(analyzer.h_xy(), 4_f64.ln(), abs <= 1e-12);
+        assert_float_eq!(analyzer.h_x_given_y(), 2_f64.ln(), abs <= 1e-12);
+        assert_float_eq!(analyzer.mutual_info(), 0., abs <= 1e-12);
+    }
+
+    #[test]
+    fn empty() {
+        let analyzer = JointAnalyzer::<usize, usize>::new(&[]);
+        assert_float_eq!(analyzer.h_xy(), 0., abs <= 1e-12);
+    }
+}
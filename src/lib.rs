@@ -11,20 +11,103 @@
 //! let mut estimator = Estimator::from(unnorm_distr);
 //! println!("Entropy estimation: {:?}", estimator.entropy()); // Random result
 //! ```
+//!
+//! # Features
+//!
+//! - `std` (enabled by default): polynomial estimators and sampling methods.
+//!   Without it, the crate is `no_std` (but needs `alloc`) and provides
+//!   naive estimations, [`count_dup`] and the traits.
+//! - `csv` (with `std`): `Estimator::from_csv_column`, to read samples from comma separated values.
+//! - `ndarray`: conversion of `ndarray` views into [`NaiveEstimator`], `Bootstrap::from_ndarray`
+//!   and `estimate_rows`, to estimate the entropy of each row of a count matrix.
+//! - `plotters` (with `std`): `plot_fit`, to draw the extrapolation of an `Estimator` to a PNG file.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "std")]
+mod batch;
+mod column;
+#[cfg(feature = "std")]
+mod dirichlet;
+#[cfg(feature = "std")]
+mod divergence;
+#[cfg(feature = "std")]
+mod entropy_rate;
+#[cfg(feature = "std")]
 mod estimator;
+mod joint;
+mod log_base;
+#[cfg(feature = "std")]
+mod mutual_info;
+mod naive;
+#[cfg(all(feature = "std", feature = "plotters"))]
+mod plot;
+#[cfg(feature = "std")]
 mod sampling_method;
+mod sequence;
+#[cfg(feature = "std")]
+mod shrinkage;
+#[cfg(feature = "std")]
+mod streaming;
 mod traits;
 mod utils;
+#[cfg(feature = "std")]
+mod window;
 
-pub use estimator::{DirectEstimator, Estimator, NaiveEstimator};
-pub use sampling_method::{Bootstrap, FixedPartition};
-pub use traits::SamplingMethod;
-pub use utils::count_dup;
+#[cfg(all(feature = "std", feature = "ndarray"))]
+pub use batch::estimate_rows;
+#[cfg(feature = "std")]
+pub use batch::{estimate_batch, BootstrapConfig};
+pub use column::entropy_from_column;
+#[cfg(feature = "std")]
+pub use dirichlet::dirichlet_multinomial_entropy;
+#[cfg(feature = "std")]
+pub use divergence::KlEstimator;
+#[cfg(feature = "std")]
+pub use entropy_rate::{block_entropy, EntropyRateEstimator};
+#[cfg(all(feature = "std", feature = "csv"))]
+pub use estimator::ReadError;
+#[cfg(feature = "std")]
+pub use estimator::{
+    compare_backends, DirectEstimator, EntropyEstimate, EntropyFit, Estimator, FitDiagnostics,
+    FittingError, FixedDegreeEstimator, ParameterError, SeedSensitivity,
+};
+pub use joint::JointAnalyzer;
+pub use log_base::{InvalidBase, LogBase};
+#[cfg(feature = "std")]
+pub use mutual_info::{ConditionalEntropyEstimator, MutualInfoEstimator};
+pub use naive::{
+    entropy_from_u64, entropy_with_rare_bucket, weighted_entropy, BiasCorrection, NaiveEstimator,
+    NullDistribution, OwnedNaiveEstimator, WeightsError,
+};
+#[cfg(all(feature = "std", feature = "plotters"))]
+pub use plot::{plot_fit, PlotError};
+#[cfg(feature = "std")]
+pub use sampling_method::{
+    Bootstrap, BootstrapBuilder, ConstructionError, FixedPartition, ScheduleError, TooManySamples,
+    WeightedBootstrap,
+};
+pub use sequence::block_entropy_scaling;
+#[cfg(feature = "std")]
+pub use shrinkage::shrinkage_entropy;
+#[cfg(feature = "std")]
+pub use streaming::StreamingEstimator;
+pub use traits::{Count, Reseed, SamplingMethod};
+pub use utils::{count_dup, count_dup_iter, count_dup_map};
+#[cfg(feature = "std")]
+pub use window::{EntropyWindowExt, EntropyWindows};
 
 pub mod prelude {
     pub use crate::{
-        count_dup, Bootstrap, Estimator, FixedPartition, NaiveEstimator, SamplingMethod,
+        block_entropy_scaling, count_dup, count_dup_iter, count_dup_map, Count, JointAnalyzer,
+        NaiveEstimator, OwnedNaiveEstimator, SamplingMethod,
+    };
+    #[cfg(feature = "std")]
+    pub use crate::{
+        dirichlet_multinomial_entropy, Bootstrap, EntropyEstimate, Estimator, FixedPartition,
+        WeightedBootstrap,
     };
 }
 
@@ -32,11 +115,91 @@ pub mod prelude {
 mod test {
     use rand::RngCore;
 
+    #[cfg(feature = "std")]
+    use crate::{FixedPartition, SamplingMethod};
+
     /// Construct a deterministic RNG with the given seed
-    pub(crate) fn rng(seed: u64) -> impl RngCore {
+    pub(crate) fn rng(seed: u64) -> impl RngCore + Clone {
         // For tests, we want a statistically good, fast, reproducible RNG.
         // PCG32 will do fine, and will be easy to embed if we ever need to.
         const INC: u64 = 11634580027462260723;
         rand_pcg::Pcg32::new(seed, INC)
     }
+
+    /// Deterministic sampling method of degree two,
+    /// with three groups of sizes `4, 3, 2` repeated `1, 2, 2` times.
+    #[cfg(feature = "std")]
+    pub(crate) fn fixed_partition() -> FixedPartition {
+        let samples = [0, 0, 0, 1, 1, 2, 0, 1, 2, 3, 0, 0, 1, 2];
+        FixedPartition::new(&samples, &[4, 3, 2], &[1, 2, 2], 2).unwrap()
+    }
+
+    /// Sampling method of degree one whose naive entropies are `2 - 3 / n`,
+    /// up to an alternating perturbation of size `noise`,
+    /// while those of the smallest subsamples are also off by `outlier`.
+    #[cfg(feature = "std")]
+    pub(crate) struct Linear {
+        pub(crate) size_subsamples: Vec<usize>,
+        pub(crate) samples_rep: Vec<usize>,
+        pub(crate) noise: f64,
+        pub(crate) outlier: f64,
+    }
+
+    #[cfg(feature = "std")]
+    impl SamplingMethod for Linear {
+        type Count = usize;
+        type DegreeError = std::fmt::Error;
+        type NumGroupsError = std::fmt::Error;
+        type UnnormDistrError = std::fmt::Error;
+
+        fn degree(&self) -> usize {
+            1
+        }
+        fn set_degree(&mut self, _degree: usize) -> Result<&mut Self, Self::DegreeError> {
+            Err(std::fmt::Error)
+        }
+        fn num_groups(&self) -> usize {
+            self.size_subsamples.len()
+        }
+        fn set_num_groups(
+            &mut self,
+            _num_groups: usize,
+        ) -> Result<&mut Self, Self::NumGroupsError> {
+            Err(std::fmt::Error)
+        }
+        fn set_unnorm_distr(
+            &mut self,
+            _unnorm_distr: &[usize],
+        ) -> Result<&mut Self, Self::UnnormDistrError> {
+            Err(std::fmt::Error)
+        }
+        fn size_subsamples(&self) -> Vec<usize> {
+            self.size_subsamples.clone()
+        }
+        fn samples_rep(&self) -> Vec<usize> {
+            self.samples_rep.clone()
+        }
+        fn pooled_unnorm_distr(&self) -> Vec<usize> {
+            let half = self.size_subsamples[0] / 2;
+            vec![half, self.size_subsamples[0] - half]
+        }
+        fn naive_entropies(&mut self) -> Vec<(usize, f64)> {
+            let smallest = self.size_subsamples.iter().copied().min();
+            self.size_subsamples
+                .iter()
+                .zip(&self.samples_rep)
+                .flat_map(|(&size, &rep)| vec![size; rep])
+                .enumerate()
+                .map(|(i, size)| {
+                    let sign = if i % 2 == 0 { 1. } else { -1. };
+                    let outlier = if Some(size) == smallest {
+                        self.outlier
+                    } else {
+                        0.
+                    };
+                    (size, 2. - 3. / size as f64 + sign * self.noise + outlier)
+                })
+                .collect()
+        }
+    }
 }
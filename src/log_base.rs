@@ -0,0 +1,125 @@
+use core::fmt;
+use num_traits::Float;
+
+/// Base of the logarithm in which entropy is expressed.
+///
+/// All entropy computations are done in nats (natural logarithm),
+/// results in other bases are obtained by dividing by `ln(base)`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LogBase {
+    /// Natural logarithm.
+    #[default]
+    Nats,
+    /// Logarithm in base 2.
+    Bits,
+    /// Logarithm in base 10.
+    Dits,
+    /// Logarithm in an arbitrary base.
+    ///
+    /// The base must be finite, positive and different from one,
+    /// prefer [`LogBase::custom`] to check it.
+    ///
+    /// [`LogBase::custom`]: #method.custom
+    Custom(f64),
+}
+
+/// The base of a logarithm is not finite, positive and different from one.
+#[derive(Debug)]
+pub struct InvalidBase;
+
+impl fmt::Display for InvalidBase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid logarithm base: it must be finite, positive and different from one."
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidBase {}
+
+impl LogBase {
+    /// Constructs a logarithm in base `base`.
+    ///
+    /// # Errors
+    ///
+    /// If `base` is not finite, is not positive, or is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::LogBase;
+    /// let base = LogBase::custom(4.).unwrap();
+    /// assert!((base.from_nats(4_f64.ln()) - 1.).abs() < 1e-12);
+    /// assert!(LogBase::custom(1.).is_err());
+    /// ```
+    pub fn custom(base: f64) -> Result<Self, InvalidBase> {
+        if base.is_finite() && base > 0. && base != 1. {
+            Ok(LogBase::Custom(base))
+        } else {
+            Err(InvalidBase)
+        }
+    }
+
+    /// Returns the natural logarithm of the base.
+    ///
+    /// # Panics
+    ///
+    /// If the base of [`Custom`] is not valid, see [`custom`].
+    ///
+    /// [`Custom`]: #variant.Custom
+    /// [`custom`]: #method.custom
+    pub fn ln(&self) -> f64 {
+        match *self {
+            LogBase::Nats => 1.,
+            LogBase::Bits => core::f64::consts::LN_2,
+            LogBase::Dits => core::f64::consts::LN_10,
+            LogBase::Custom(base) => match LogBase::custom(base) {
+                Ok(_) => Float::ln(base),
+                Err(error) => panic!("{}", error),
+            },
+        }
+    }
+
+    /// Converts an entropy in nats to this base.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`ln`].
+    ///
+    /// [`ln`]: #method.ln
+    pub fn from_nats(&self, entropy: f64) -> f64 {
+        entropy / self.ln()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+    use test_case::test_case;
+
+    #[test_case(LogBase::Nats, 8_f64.ln(); "nats")]
+    #[test_case(LogBase::Bits, 3.; "bits")]
+    #[test_case(LogBase::Dits, 8_f64.log10(); "dits")]
+    #[test_case(LogBase::Custom(8.), 1.; "custom")]
+    fn from_nats(base: LogBase, expected: f64) {
+        assert_float_eq!(base.from_nats(8_f64.ln()), expected, abs <= 1e-12);
+    }
+
+    #[test_case(0.; "zero")]
+    #[test_case(-2.; "negative")]
+    #[test_case(1.; "one")]
+    #[test_case(f64::INFINITY; "infinite")]
+    #[test_case(f64::NAN; "nan")]
+    fn custom_invalid(base: f64) {
+        assert!(LogBase::custom(base).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn ln_invalid_custom() {
+        LogBase::Custom(1.).ln();
+    }
+}
@@ -0,0 +1,241 @@
+use core::hash::Hash;
+use rand_pcg::Pcg32;
+use std::collections::HashMap;
+
+use crate::{estimator::FittingError, utils::count_dup_ordered, Bootstrap, Estimator};
+
+/// Mutual information of paired samples, from extrapolated entropies.
+///
+/// The mutual information `I(X; Y) = H(X) + H(Y) - H(X, Y)` is computed
+/// with an [`Estimator`] for each of the marginals of `X` and `Y`
+/// and another one for the joint distribution of `(X, Y)`.
+///
+/// # Remarks
+///
+/// Default values are given to tunable parameters, as in `Estimator::from_seed`.
+/// For the naive mutual information, see [`JointAnalyzer`].
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::MutualInfoEstimator;
+/// let pairs: Vec<(usize, char)> = (0..100).map(|i| (i % 4, ['a', 'b'][i % 2])).collect();
+/// let mut estimator = MutualInfoEstimator::from_pairs(&pairs);
+/// println!("Mutual information: {:?}", estimator.mutual_information()); // Random result
+/// ```
+///
+/// [`Estimator`]: struct.Estimator.html
+/// [`JointAnalyzer`]: struct.JointAnalyzer.html
+#[derive(Debug)]
+pub struct MutualInfoEstimator {
+    x: Estimator<Bootstrap<Pcg32>>,
+    y: Estimator<Bootstrap<Pcg32>>,
+    xy: Estimator<Bootstrap<Pcg32>>,
+}
+
+impl MutualInfoEstimator {
+    /// Constructs a new `MutualInfoEstimator` from paired samples,
+    /// with a random seed.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples for the default number of groups.
+    pub fn from_pairs<X, Y>(pairs: &[(X, Y)]) -> Self
+    where
+        X: Hash + Eq + Clone,
+        Y: Hash + Eq + Clone,
+    {
+        Self::from_pairs_with_seed(pairs, rand::random())
+    }
+
+    /// Constructs a new `MutualInfoEstimator` from paired samples,
+    /// using random number generators seeded with `seed`.
+    ///
+    /// Counts are in order of first appearance in `pairs`,
+    /// so that estimations are reproducible.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples for the default number of groups.
+    pub fn from_pairs_with_seed<X, Y>(pairs: &[(X, Y)], seed: u64) -> Self
+    where
+        X: Hash + Eq + Clone,
+        Y: Hash + Eq + Clone,
+    {
+        let xs: Vec<X> = pairs.iter().map(|(x, _)| x.clone()).collect();
+        let ys: Vec<Y> = pairs.iter().map(|(_, y)| y.clone()).collect();
+
+        MutualInfoEstimator {
+            x: Estimator::from_seed(&count_dup_ordered(&xs), seed),
+            y: Estimator::from_seed(&count_dup_ordered(&ys), seed),
+            xy: Estimator::from_seed(&count_dup_ordered(pairs), seed),
+        }
+    }
+
+    /// Estimates the mutual information `I(X; Y) = H(X) + H(Y) - H(X, Y)`.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities in any of the three estimations.
+    pub fn mutual_information(&mut self) -> Result<f64, FittingError> {
+        Ok(self.x.entropy()? + self.y.entropy()? - self.xy.entropy()?)
+    }
+}
+
+/// Conditional entropy of paired samples, from extrapolated entropies.
+///
+/// The conditional entropy `H(Y | X) = H(X, Y) - H(X)` is computed
+/// with an [`Estimator`] for the joint distribution of `(X, Y)`
+/// and another one for the marginal of `X`.
+///
+/// # Remarks
+///
+/// Both estimators use the same seed and their unnormalized distributions
+/// are aligned, so that each subsample of `X` is exactly the marginal of
+/// the corresponding subsample of `(X, Y)`.
+/// This way, the sampling noise mostly cancels in the difference.
+///
+/// Default values are given to tunable parameters, as in `Estimator::from_seed`.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::ConditionalEntropyEstimator;
+/// let pairs: Vec<(usize, char)> = (0..100).map(|i| (i % 4, ['a', 'b'][i % 3 % 2])).collect();
+/// let mut estimator = ConditionalEntropyEstimator::from_pairs(&pairs);
+/// println!("Conditional entropy: {:?}", estimator.conditional_entropy()); // Random result
+/// ```
+///
+/// [`Estimator`]: struct.Estimator.html
+#[derive(Debug)]
+pub struct ConditionalEntropyEstimator {
+    x: Estimator<Bootstrap<Pcg32>>,
+    xy: Estimator<Bootstrap<Pcg32>>,
+}
+
+impl ConditionalEntropyEstimator {
+    /// Constructs a new `ConditionalEntropyEstimator` from paired samples,
+    /// with a random seed.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples for the default number of groups.
+    pub fn from_pairs<X, Y>(pairs: &[(X, Y)]) -> Self
+    where
+        X: Hash + Eq + Clone,
+        Y: Hash + Eq + Clone,
+    {
+        Self::from_pairs_with_seed(pairs, rand::random())
+    }
+
+    /// Constructs a new `ConditionalEntropyEstimator` from paired samples,
+    /// using random number generators seeded with `seed`.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples for the default number of groups.
+    pub fn from_pairs_with_seed<X, Y>(pairs: &[(X, Y)], seed: u64) -> Self
+    where
+        X: Hash + Eq + Clone,
+        Y: Hash + Eq + Clone,
+    {
+        let (x_unnorm_distr, xy_unnorm_distr) = aligned_counts(pairs);
+
+        ConditionalEntropyEstimator {
+            x: Estimator::from_seed(&x_unnorm_distr, seed),
+            xy: Estimator::from_seed(&xy_unnorm_distr, seed),
+        }
+    }
+
+    /// Estimates the conditional entropy `H(Y | X) = H(X, Y) - H(X)`.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities in any of the two estimations.
+    pub fn conditional_entropy(&mut self) -> Result<f64, FittingError> {
+        Ok(self.xy.entropy()? - self.x.entropy()?)
+    }
+}
+
+/// Unnormalized distributions of `X` and `(X, Y)`, where the counts of `(X, Y)`
+/// are grouped by `X`, in the same order as the counts of `X`.
+fn aligned_counts<X, Y>(pairs: &[(X, Y)]) -> (Vec<usize>, Vec<usize>)
+where
+    X: Hash + Eq + Clone,
+    Y: Hash + Eq + Clone,
+{
+    let mut x_indices = HashMap::new();
+    let mut groups: Vec<HashMap<Y, usize>> = Vec::new();
+    for (x, y) in pairs {
+        let index = *x_indices.entry(x.clone()).or_insert_with(|| {
+            groups.push(HashMap::new());
+            groups.len() - 1
+        });
+        *groups[index].entry(y.clone()).or_insert(0) += 1;
+    }
+
+    let x_unnorm_distr = groups.iter().map(|group| group.values().sum()).collect();
+    let xy_unnorm_distr = groups
+        .iter()
+        .flat_map(|group| group.values().copied())
+        .collect();
+    (x_unnorm_distr, xy_unnorm_distr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn independent() {
+        let pairs: Vec<(usize, usize)> = (0..400).map(|i| (i % 4, (i / 4) % 4)).collect();
+        let mut estimator = MutualInfoEstimator::from_pairs_with_seed(&pairs, 1);
+
+        assert_float_eq!(estimator.mutual_information().unwrap(), 0., abs <= 0.1);
+    }
+
+    #[test]
+    fn identity() {
+        let pairs: Vec<(usize, usize)> = (0..400).map(|i| (i % 4, i % 4)).collect();
+        let mut estimator = MutualInfoEstimator::from_pairs_with_seed(&pairs, 1);
+
+        assert_float_eq!(
+            estimator.mutual_information().unwrap(),
+            4_f64.ln(),
+            abs <= 0.1
+        );
+    }
+
+    #[test]
+    fn deterministic_mapping() {
+        let pairs: Vec<(usize, bool)> = (0..400).map(|i| (i % 8, i % 8 < 3)).collect();
+        let mut estimator = ConditionalEntropyEstimator::from_pairs_with_seed(&pairs, 1);
+
+        assert_float_eq!(estimator.conditional_entropy().unwrap(), 0., abs <= 1e-9);
+    }
+
+    #[test]
+    fn independent_conditional() {
+        let pairs: Vec<(usize, usize)> = (0..400).map(|i| (i % 4, (i / 4) % 2)).collect();
+        let mut estimator = ConditionalEntropyEstimator::from_pairs_with_seed(&pairs, 1);
+
+        assert_float_eq!(
+            estimator.conditional_entropy().unwrap(),
+            2_f64.ln(),
+            abs <= 0.05
+        );
+    }
+
+    #[test]
+    fn aligned() {
+        let pairs = [(0, 'a'), (1, 'b'), (0, 'b'), (0, 'a'), (2, 'c')];
+        let (x_unnorm_distr, xy_unnorm_distr) = aligned_counts(&pairs);
+
+        assert_eq!(x_unnorm_distr, vec![3, 1, 1]);
+        let mut first_group = xy_unnorm_distr[..2].to_vec();
+        first_group.sort_unstable();
+        assert_eq!(first_group, vec![1, 2]);
+        assert_eq!(xy_unnorm_distr[2..], [1, 1]);
+    }
+}
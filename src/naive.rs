@@ -0,0 +1,1142 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{fmt, marker::PhantomData};
+#[cfg(feature = "ndarray")]
+use ndarray::ArrayView1;
+use num_traits::Float;
+
+use crate::{
+    traits::Count,
+    utils::{checked_total, digamma},
+    LogBase,
+};
+
+/// Naive entropy estimator.
+///
+/// It assumes the empirical distribution is almost the real distribution,
+/// which is asymptotically true.
+///
+/// Counts are integers by default, but any [`Count`] is accepted.
+/// Computations are done in `f64` by default,
+/// see [`with_float`] to use another floating point type.
+///
+/// [`Count`]: trait.Count.html
+/// [`with_float`]: #method.with_float
+#[derive(Debug)]
+pub struct NaiveEstimator<'a, C = usize, F = f64> {
+    unnorm_distr: &'a [C],
+    float: PhantomData<F>,
+}
+
+/// Bias correction applied to the naive entropy estimation.
+///
+/// See [`entropy_corrected`].
+///
+/// [`entropy_corrected`]: struct.NaiveEstimator.html#method.entropy_corrected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BiasCorrection {
+    /// No correction, the plug-in estimation.
+    #[default]
+    None,
+    /// Miller-Madow correction.
+    MillerMadow,
+    /// Grassberger correction.
+    Grassberger,
+}
+
+/// Reason why an unnormalized distribution has no samples.
+#[derive(Debug)]
+pub enum NullDistribution {
+    /// There are no entries.
+    EmptyDistribution,
+    /// There are entries, but they add up to zero.
+    ZeroTotal,
+}
+
+impl fmt::Display for NullDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NullDistribution::EmptyDistribution => write!(
+                f,
+                "Invalid unnormalized distribution: it is empty, there must be at least one sample."
+            ),
+            NullDistribution::ZeroTotal => write!(
+                f,
+                "Invalid unnormalized distribution: all entries are zero, there must be at least one sample."
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NullDistribution {}
+
+/// Reason why a weighted distribution can not be used.
+#[derive(Debug)]
+pub enum WeightsError {
+    /// Some weight is negative or not finite.
+    InvalidWeight,
+    /// The underlying distribution has no samples.
+    NullDistribution(NullDistribution),
+}
+
+impl fmt::Display for WeightsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeightsError::InvalidWeight => {
+                write!(f, "Invalid weights: they must be finite and non-negative.")
+            }
+            WeightsError::NullDistribution(error) => error.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WeightsError {}
+
+impl From<NullDistribution> for WeightsError {
+    fn from(error: NullDistribution) -> Self {
+        WeightsError::NullDistribution(error)
+    }
+}
+
+impl<'a, C> NaiveEstimator<'a, C>
+where
+    C: Count,
+{
+    /// Construct a new `NaiveEstimator`.
+    ///
+    /// # Errors
+    ///
+    /// If `unnorm_distr` is empty, or if all its entries are zero.
+    pub fn new(unnorm_distr: &'a [C]) -> Result<Self, NullDistribution> {
+        if unnorm_distr.is_empty() {
+            return Err(NullDistribution::EmptyDistribution);
+        }
+        if unnorm_distr.iter().map(|x| x.to_f64()).sum::<f64>() <= 0. {
+            return Err(NullDistribution::ZeroTotal);
+        }
+        Ok(NaiveEstimator::new_unchecked(unnorm_distr))
+    }
+
+    pub fn new_unchecked(unnorm_distr: &'a [C]) -> Self {
+        NaiveEstimator {
+            unnorm_distr: unnorm_distr,
+            float: PhantomData,
+        }
+    }
+
+    /// Number of observed elements `K`, i.e. of non-zero entries.
+    pub fn support_size(&self) -> usize {
+        self.unnorm_distr.iter().filter(|x| x.to_f64() > 0.).count()
+    }
+
+    /// Plug-in entropy of the posterior mean under a symmetric Dirichlet prior,
+    /// i.e. the naive entropy of the counts `n_i + prior`
+    /// over `support_size` elements.
+    ///
+    /// Elements never observed must still be counted in `support_size`,
+    /// each of them contributing a count of `prior`.
+    /// Common priors are `1` (Laplace) and `0.5` (Jeffreys).
+    /// The bigger the prior, the closer the estimation is to `ln(support_size)`.
+    ///
+    /// # Remarks
+    ///
+    /// If `support_size` is smaller than the length of the unnormalized distribution,
+    /// the length of the unnormalized distribution is used instead.
+    ///
+    /// # Panics
+    ///
+    /// If `prior` is negative or not finite.
+    pub fn entropy_bayesian(&self, prior: f64, support_size: usize) -> f64 {
+        assert!(
+            prior.is_finite() && prior >= 0.,
+            "the prior must be finite and non-negative"
+        );
+        let support_size = support_size.max(self.unnorm_distr.len());
+        let unobserved = (support_size - self.unnorm_distr.len()) as f64;
+        let all =
+            self.unnorm_distr.iter().map(|x| x.to_f64()).sum::<f64>() + prior * support_size as f64;
+        let x_ln_x = |count: f64| {
+            if count > 0. {
+                count * count.ln()
+            } else {
+                0.
+            }
+        };
+
+        let sum_x_ln_x: f64 = self
+            .unnorm_distr
+            .iter()
+            .map(|x| x_ln_x(x.to_f64() + prior))
+            .sum::<f64>()
+            + unobserved * x_ln_x(prior);
+        all.ln() - sum_x_ln_x / all
+    }
+
+    /// Naive entropy, expressed in the logarithm base `base`.
+    pub fn entropy_with_base(&self, base: LogBase) -> f64 {
+        base.from_nats(self.entropy())
+    }
+
+    /// Naive perplexity, `exp(H)`.
+    ///
+    /// It is the number of equally likely elements with the same entropy.
+    pub fn perplexity(&self) -> f64 {
+        self.entropy().exp()
+    }
+
+    /// Naive entropy divided by its maximum, `H / ln(K)`,
+    /// where `K` is the number of observed elements.
+    ///
+    /// It is between `0` and `1`, and one for uniform distributions,
+    /// which allows to compare distributions over different numbers of elements.
+    ///
+    /// # Remarks
+    ///
+    /// If at most one element was observed, zero is returned.
+    pub fn normalized_entropy(&self) -> f64 {
+        let observed = self.support_size();
+        if observed <= 1 {
+            return 0.;
+        }
+        self.entropy() / (observed as f64).ln()
+    }
+
+    /// Naive entropy with the Miller-Madow bias correction,
+    /// `H + (K - 1) / (2 N)`, where `K` is the number of observed elements
+    /// (non-zero entries) and `N` the total number of samples.
+    pub fn entropy_miller_madow(&self) -> f64 {
+        let all = self.unnorm_distr.iter().map(|x| x.to_f64()).sum::<f64>();
+        let observed = self.support_size() as f64;
+        self.entropy() + (observed - 1.) / (2. * all)
+    }
+
+    /// Naive Rényi entropy of order `alpha`, `ln(Σ p_i^alpha) / (1 - alpha)`.
+    ///
+    /// Special orders are handled by their limits:
+    /// - for `alpha = 0`, it is `ln(K)`, where `K` is the number of observed elements,
+    /// - for `alpha` close to `1`, it is the Shannon entropy given by [`entropy`],
+    /// - for `alpha = ∞`, it is the min-entropy `-ln(max_i p_i)`.
+    ///
+    /// [`entropy`]: #method.entropy
+    pub fn renyi_entropy(&self, alpha: f64) -> f64 {
+        if (alpha - 1.).abs() < 1e-12 {
+            return self.entropy();
+        }
+        let all = self.unnorm_distr.iter().map(|x| x.to_f64()).sum::<f64>();
+        let probabilities = self
+            .unnorm_distr
+            .iter()
+            .map(|x| x.to_f64() / all)
+            .filter(|&probability| probability > 0.);
+        if alpha == 0. {
+            return (probabilities.count() as f64).ln();
+        }
+
+        // Factoring out the biggest probability avoids underflow for big orders
+        let max_probability = probabilities.clone().fold(0., f64::max);
+        if alpha.is_infinite() {
+            return self.min_entropy();
+        }
+        let ln_sum = alpha * max_probability.ln()
+            + probabilities
+                .map(|probability| (probability / max_probability).powf(alpha))
+                .sum::<f64>()
+                .ln();
+        ln_sum / (1. - alpha)
+    }
+
+    /// Naive collision entropy, i.e. Rényi entropy of order two, `-ln(Σ p_i^2)`.
+    ///
+    /// It is computed exactly, without the general path of [`renyi_entropy`].
+    ///
+    /// # Remarks
+    ///
+    /// It is always smaller or equal than the Shannon entropy given by [`entropy`].
+    ///
+    /// [`renyi_entropy`]: #method.renyi_entropy
+    /// [`entropy`]: #method.entropy
+    pub fn collision_entropy(&self) -> f64 {
+        let all = self.unnorm_distr.iter().map(|x| x.to_f64()).sum::<f64>();
+        let sum_squares: f64 = self
+            .unnorm_distr
+            .iter()
+            .map(|x| {
+                let probability = x.to_f64() / all;
+                probability * probability
+            })
+            .sum();
+        -sum_squares.ln()
+    }
+
+    /// Naive min-entropy, `-ln(max_i p_i)`.
+    ///
+    /// # Remarks
+    ///
+    /// It is a lower bound of the other entropies:
+    /// `min_entropy <= entropy <= ln(K)`,
+    /// where `K` is the number of observed elements.
+    pub fn min_entropy(&self) -> f64 {
+        let all = self.unnorm_distr.iter().map(|x| x.to_f64()).sum::<f64>();
+        let max_count = self
+            .unnorm_distr
+            .iter()
+            .map(|x| x.to_f64())
+            .fold(0., f64::max);
+        -(max_count / all).ln()
+    }
+
+    /// Asymptotic variance of the naive entropy estimation,
+    /// `(Σ p_i ln(p_i)^2 - H^2) / N`, where `N` is the total number of samples.
+    ///
+    /// This is the first order (delta method) approximation,
+    /// valid when the number of samples is large compared to the number of elements.
+    /// It is zero for a distribution with a single element.
+    pub fn entropy_variance(&self) -> f64 {
+        let all = self.unnorm_distr.iter().map(|x| x.to_f64()).sum::<f64>();
+        let entropy = self.entropy();
+        let second_moment: f64 = self
+            .unnorm_distr
+            .iter()
+            .map(|x| x.to_f64() / all)
+            .filter(|&probability| probability > 0.)
+            .map(|probability| probability * probability.ln().powi(2))
+            .sum();
+        (second_moment - entropy.powi(2)).max(0.) / all
+    }
+}
+
+impl<'a, C, F> NaiveEstimator<'a, C, F>
+where
+    C: Count,
+    F: Float,
+{
+    pub fn entropy(&self) -> F {
+        let mut entropy = F::zero();
+
+        let all = self
+            .unnorm_distr
+            .iter()
+            .fold(F::zero(), |acc, x| acc + to_float(x));
+        for repetitions in self.unnorm_distr.iter().map(to_float::<C, F>) {
+            // Empty bins contribute nothing, by the convention `0 ln(0) = 0`
+            if repetitions == F::zero() {
+                continue;
+            }
+            entropy = entropy - repetitions * (repetitions.ln() - all.ln());
+        }
+        entropy / all
+    }
+
+    /// Changes the floating point type used for computations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::NaiveEstimator;
+    /// let unnorm_distr = [1, 2, 3, 4];
+    /// let naive_estimator = NaiveEstimator::new(&unnorm_distr).unwrap();
+    /// let entropy: f32 = naive_estimator.with_float::<f32>().entropy();
+    /// ```
+    pub fn with_float<G>(self) -> NaiveEstimator<'a, C, G> {
+        NaiveEstimator {
+            unnorm_distr: self.unnorm_distr,
+            float: PhantomData,
+        }
+    }
+}
+
+fn to_float<C, F>(count: &C) -> F
+where
+    C: Count,
+    F: Float,
+{
+    F::from(count.to_f64()).unwrap() // Never fails for floating point types
+}
+
+impl<'a> NaiveEstimator<'a, usize> {
+    /// Total number of samples `N`, i.e. the sum of all entries,
+    /// or `None` if it does not fit in a `usize`.
+    pub fn total_count(&self) -> Option<usize> {
+        checked_total(self.unnorm_distr)
+    }
+
+    /// Entropy estimation with the bias correction of Grassberger[^1],
+    /// `ln(N) - (1/N) Σ n_i G(n_i)`, where
+    /// `G(n) = ψ(n) + (-1)^n (ψ((n + 1) / 2) - ψ(n / 2)) / 2`
+    /// and `ψ` is the digamma function.
+    ///
+    /// It corrects the small-sample bias of the naive estimation better
+    /// than [`entropy_miller_madow`].
+    ///
+    /// [`entropy_miller_madow`]: #method.entropy_miller_madow
+    /// [^1]: https://arxiv.org/abs/physics/0307138
+    pub fn entropy_grassberger(&self) -> f64 {
+        // Accumulated in floating point, since the sum of counts may overflow
+        let all: f64 = self.unnorm_distr.iter().map(|&count| count as f64).sum();
+        let correction: f64 = self
+            .unnorm_distr
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let n = count as f64;
+                let sign = if count % 2 == 0 { 1. } else { -1. };
+                let g = digamma(n) + 0.5 * sign * (digamma((n + 1.) / 2.) - digamma(n / 2.));
+                n * g
+            })
+            .sum();
+        all.ln() - correction / all
+    }
+
+    /// Entropy estimation with the bias correction `method`.
+    ///
+    /// It dispatches to [`entropy`], [`entropy_miller_madow`] or [`entropy_grassberger`],
+    /// which is useful to choose the correction at runtime.
+    ///
+    /// [`entropy`]: #method.entropy
+    /// [`entropy_miller_madow`]: #method.entropy_miller_madow
+    /// [`entropy_grassberger`]: #method.entropy_grassberger
+    pub fn entropy_corrected(&self, method: BiasCorrection) -> f64 {
+        match method {
+            BiasCorrection::None => self.entropy(),
+            BiasCorrection::MillerMadow => self.entropy_miller_madow(),
+            BiasCorrection::Grassberger => self.entropy_grassberger(),
+        }
+    }
+
+    /// Entropy estimation with the jackknife bias correction,
+    /// `N H - ((N - 1) / N) Σ n_i H_{-i}`, where `H_{-i}` is the naive entropy
+    /// after removing one sample of the `i`-th element.
+    ///
+    /// Removing the only sample of an element makes it disappear,
+    /// which is accounted for by the convention `0 ln(0) = 0`.
+    ///
+    /// # Remarks
+    ///
+    /// With a single sample, there is nothing to correct and the naive entropy is returned.
+    pub fn entropy_jackknife(&self) -> f64 {
+        // Accumulated in `u128`, since the sum of counts may overflow a `usize`
+        let all: u128 = self.unnorm_distr.iter().map(|&count| count as u128).sum();
+        if all <= 1 {
+            return self.entropy();
+        }
+        let x_ln_x = |count: usize| {
+            if count == 0 {
+                0.
+            } else {
+                count as f64 * (count as f64).ln()
+            }
+        };
+
+        // Naive entropy is `ln(N) - (Σ n_j ln(n_j)) / N`
+        let sum_x_ln_x: f64 = self.unnorm_distr.iter().map(|&count| x_ln_x(count)).sum();
+        let entropy = (all as f64).ln() - sum_x_ln_x / all as f64;
+        let rest = (all - 1) as f64;
+        let sum_leave_one_out: f64 = self
+            .unnorm_distr
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let sum_x_ln_x = sum_x_ln_x - x_ln_x(count) + x_ln_x(count - 1);
+                count as f64 * (rest.ln() - sum_x_ln_x / rest)
+            })
+            .sum();
+
+        all as f64 * entropy - rest / all as f64 * sum_leave_one_out
+    }
+
+    /// Frequency of frequencies: for each observed count, the number of elements
+    /// with exactly that count.
+    ///
+    /// Elements that were not observed (zero entries) are left out.
+    /// This is the sufficient statistic of coverage-based estimations,
+    /// like [`unseen_mass`] and [`entropy_chao_shen`].
+    ///
+    /// [`unseen_mass`]: #method.unseen_mass
+    /// [`entropy_chao_shen`]: #method.entropy_chao_shen
+    pub fn frequency_spectrum(&self) -> BTreeMap<usize, usize> {
+        let mut spectrum = BTreeMap::new();
+        for &count in self.unnorm_distr.iter().filter(|&&count| count > 0) {
+            *spectrum.entry(count).or_insert(0) += 1;
+        }
+        spectrum
+    }
+
+    /// Good-Turing estimation of the total probability of unseen elements,
+    /// `f_1 / N`, where `f_1` is the number of elements observed only once.
+    ///
+    /// A high value warns that the support is undersampled,
+    /// so that entropy estimations (extrapolated or not) are less reliable.
+    /// One minus it is the estimated sample coverage used in [`entropy_chao_shen`].
+    ///
+    /// [`entropy_chao_shen`]: #method.entropy_chao_shen
+    pub fn unseen_mass(&self) -> f64 {
+        // Accumulated in floating point, since the sum of counts may overflow
+        let all: f64 = self.unnorm_distr.iter().map(|&count| count as f64).sum();
+        let singletons = self
+            .unnorm_distr
+            .iter()
+            .filter(|&&count| count == 1)
+            .count() as f64;
+        singletons / all
+    }
+
+    /// Coverage-adjusted entropy estimation of Chao and Shen[^1],
+    /// `- Σ p̃_i ln(p̃_i) / (1 - (1 - p̃_i)^N)`, where `p̃_i = C n_i / N`
+    /// and `C = 1 - f_1 / N` is the Good-Turing estimation of the sample coverage,
+    /// `f_1` being the number of elements observed only once.
+    ///
+    /// The coverage accounts for unseen elements,
+    /// and the denominator for the probability of each element to be observed.
+    ///
+    /// # Remarks
+    ///
+    /// If all elements are singletons, the coverage would be zero,
+    /// so `f_1 = N - 1` is used instead.
+    ///
+    /// [^1]: https://doi.org/10.1023/A:1026096204727
+    pub fn entropy_chao_shen(&self) -> f64 {
+        // Accumulated in floating point, since the sum of counts may overflow
+        let all: f64 = self.unnorm_distr.iter().map(|&count| count as f64).sum();
+        let mut singletons = self
+            .unnorm_distr
+            .iter()
+            .filter(|&&count| count == 1)
+            .count() as f64;
+        if singletons == all {
+            singletons = all - 1.;
+        }
+        let coverage = 1. - singletons / all;
+        self.horvitz_thompson(coverage)
+    }
+
+    /// Horvitz-Thompson entropy estimation,
+    /// `- Σ p_i ln(p_i) / (1 - (1 - p_i)^N)`, where `p_i = n_i / N`.
+    ///
+    /// Each term of the naive entropy is inflated by the inverse of the probability
+    /// of the element to be observed in a sample of size `N`.
+    /// It is a useful cross-check of extrapolated estimations.
+    ///
+    /// # Remarks
+    ///
+    /// For very small `N`, inclusion probabilities are underestimated,
+    /// so the entropy tends to be overestimated.
+    /// See [`entropy_chao_shen`], which also adjusts for unseen elements.
+    ///
+    /// [`entropy_chao_shen`]: #method.entropy_chao_shen
+    pub fn entropy_horvitz_thompson(&self) -> f64 {
+        self.horvitz_thompson(1.)
+    }
+
+    /// Horvitz-Thompson estimation with probabilities scaled by `coverage`.
+    fn horvitz_thompson(&self, coverage: f64) -> f64 {
+        let all: f64 = self.unnorm_distr.iter().map(|&count| count as f64).sum();
+        self.unnorm_distr
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let probability = coverage * count as f64 / all;
+                let inclusion = 1. - (1. - probability).powf(all);
+                -probability * probability.ln() / inclusion
+            })
+            .sum()
+    }
+}
+
+impl<'a> NaiveEstimator<'a, f64> {
+    /// Constructs a new `NaiveEstimator` from fractional weights,
+    /// like importance sampling weights, instead of integer counts.
+    ///
+    /// Weights are normalized to obtain the distribution.
+    ///
+    /// # Errors
+    ///
+    /// If a weight is negative or not finite, or if all weights are zero.
+    pub fn from_weights(weights: &'a [f64]) -> Result<Self, WeightsError> {
+        if weights
+            .iter()
+            .any(|weight| !weight.is_finite() || *weight < 0.)
+        {
+            return Err(WeightsError::InvalidWeight);
+        }
+        Ok(NaiveEstimator::new(weights)?)
+    }
+}
+
+/// Naive entropy of the distribution obtained by normalizing `weights`.
+///
+/// # Errors
+///
+/// Same as [`NaiveEstimator::from_weights`].
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::weighted_entropy;
+/// let weights = [0.5, 0.5, 1.];
+/// let entropy = weighted_entropy(&weights).unwrap();
+/// # assert!((entropy - 1.5 * 2_f64.ln()).abs() < 1e-12);
+/// ```
+///
+/// [`NaiveEstimator::from_weights`]: struct.NaiveEstimator.html#method.from_weights
+pub fn weighted_entropy(weights: &[f64]) -> Result<f64, WeightsError> {
+    Ok(NaiveEstimator::from_weights(weights)?.entropy())
+}
+
+#[cfg(feature = "ndarray")]
+impl<'a> From<ArrayView1<'a, usize>> for NaiveEstimator<'a, usize> {
+    /// Performs the conversion, borrowing the unnormalized distribution in place.
+    ///
+    /// # Panics
+    ///
+    /// If the array is not contiguous, or if there are no samples.
+    fn from(unnorm_distr: ArrayView1<'a, usize>) -> Self {
+        let unnorm_distr = unnorm_distr
+            .to_slice()
+            .expect("the unnormalized distribution must be contiguous");
+        NaiveEstimator::new(unnorm_distr).unwrap()
+    }
+}
+
+/// Naive entropy estimator that owns its unnormalized distribution.
+///
+/// It is the same as [`NaiveEstimator`], but without borrowing,
+/// so it can be returned from a function that computes the counts locally.
+/// All estimations are available through [`as_naive`].
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::{count_dup, OwnedNaiveEstimator};
+/// fn make() -> OwnedNaiveEstimator {
+///     let samples = ['a', 'b', 'b', 'c'];
+///     OwnedNaiveEstimator::new(count_dup(&samples)).unwrap()
+/// }
+/// let estimator = make();
+/// println!("Entropy: {}", estimator.entropy());
+/// println!("Miller-Madow: {}", estimator.as_naive().entropy_miller_madow());
+/// ```
+///
+/// [`NaiveEstimator`]: struct.NaiveEstimator.html
+/// [`as_naive`]: #method.as_naive
+#[derive(Debug, Clone)]
+pub struct OwnedNaiveEstimator<C = usize, F = f64> {
+    unnorm_distr: Vec<C>,
+    float: PhantomData<F>,
+}
+
+impl<C> OwnedNaiveEstimator<C>
+where
+    C: Count,
+{
+    /// Construct a new `OwnedNaiveEstimator`, taking ownership of `unnorm_distr`.
+    ///
+    /// # Errors
+    ///
+    /// If there are no samples, as in [`NaiveEstimator::new`].
+    ///
+    /// [`NaiveEstimator::new`]: struct.NaiveEstimator.html#method.new
+    pub fn new(unnorm_distr: Vec<C>) -> Result<Self, NullDistribution> {
+        NaiveEstimator::new(&unnorm_distr)?;
+        Ok(OwnedNaiveEstimator::new_unchecked(unnorm_distr))
+    }
+
+    pub fn new_unchecked(unnorm_distr: Vec<C>) -> Self {
+        OwnedNaiveEstimator {
+            unnorm_distr,
+            float: PhantomData,
+        }
+    }
+}
+
+impl<C, F> OwnedNaiveEstimator<C, F>
+where
+    C: Count,
+    F: Float,
+{
+    /// Returns a [`NaiveEstimator`] borrowing the unnormalized distribution.
+    ///
+    /// [`NaiveEstimator`]: struct.NaiveEstimator.html
+    pub fn as_naive(&self) -> NaiveEstimator<'_, C, F> {
+        NaiveEstimator {
+            unnorm_distr: &self.unnorm_distr,
+            float: PhantomData,
+        }
+    }
+
+    pub fn entropy(&self) -> F {
+        self.as_naive().entropy()
+    }
+
+    /// Returns the unnormalized distribution.
+    pub fn unnorm_distr(&self) -> &[C] {
+        &self.unnorm_distr
+    }
+
+    /// Changes the floating point type used for computations.
+    pub fn with_float<G>(self) -> OwnedNaiveEstimator<C, G> {
+        OwnedNaiveEstimator {
+            unnorm_distr: self.unnorm_distr,
+            float: PhantomData,
+        }
+    }
+}
+
+/// Naive entropy of an unnormalized distribution with `u64` counts.
+///
+/// Useful when counts do not fit in a `usize` (e.g. on 32-bit targets).
+/// Counts are converted to `f64`, so that their sum never overflows.
+///
+/// # Errors
+///
+/// If there are no samples.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::entropy_from_u64;
+/// let unnorm_distr: [u64; 2] = [1 << 40, 1 << 40];
+/// let entropy = entropy_from_u64(&unnorm_distr).unwrap();
+/// # assert!((entropy - 2_f64.ln()).abs() < 1e-12);
+/// ```
+pub fn entropy_from_u64(unnorm_distr: &[u64]) -> Result<f64, NullDistribution> {
+    let float_unnorm_distr: Vec<f64> = unnorm_distr.iter().map(|&count| count as f64).collect();
+    Ok(NaiveEstimator::new(&float_unnorm_distr)?.entropy())
+}
+
+/// Naive entropy after merging all rare elements into a single "other" element.
+///
+/// Every entry of `unnorm_distr` with less than `min_count` occurrences
+/// is added to one bucket, and the naive entropy of the result is returned.
+/// With `min_count = 1`, this is the naive entropy of `unnorm_distr`.
+///
+/// # Remarks
+///
+/// Merging elements never increases entropy,
+/// so the result is non-increasing with respect to `min_count`.
+///
+/// # Errors
+///
+/// If there are no samples.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::entropy_with_rare_bucket;
+/// let unnorm_distr = [10, 10, 1, 1];
+/// let entropy = entropy_with_rare_bucket(&unnorm_distr, 2).unwrap();
+/// // Same as the naive entropy of [10, 10, 2]
+/// # assert!((entropy - 0.9347699).abs() < 1e-6);
+/// ```
+pub fn entropy_with_rare_bucket(
+    unnorm_distr: &[usize],
+    min_count: usize,
+) -> Result<f64, NullDistribution> {
+    let (rare, frequent): (Vec<usize>, Vec<usize>) = unnorm_distr
+        .iter()
+        .filter(|&&count| count > 0)
+        .partition(|&&count| count < min_count);
+
+    // Merged in floating point, since the sum of rare counts may overflow
+    let mut merged: Vec<f64> = frequent.iter().map(|&count| count as f64).collect();
+    let rare_count: f64 = rare.iter().map(|&count| count as f64).sum();
+    if rare_count > 0. {
+        merged.push(rare_count);
+    }
+    Ok(NaiveEstimator::new(&merged)?.entropy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+    use test_case::test_case;
+
+    #[test]
+    fn new_empty() {
+        let result = NaiveEstimator::<usize>::new(&[]);
+        assert!(matches!(result, Err(NullDistribution::EmptyDistribution)));
+    }
+
+    #[test_case(&[0]; "one_zero")]
+    #[test_case(&[0, 0, 0]; "zeros")]
+    fn new_zero_total(unnorm_distr: &[usize]) {
+        let result = NaiveEstimator::new(unnorm_distr);
+        assert!(matches!(result, Err(NullDistribution::ZeroTotal)));
+    }
+
+    #[test_case(&[11], 0.; "uniform_one")]
+    #[test_case(&[1; 4], 4.0_f64.ln(); "uniform_four")]
+    #[test_case(&[1; 8], 8.0_f64.ln(); "uniform_eight")]
+    #[test_case(&[1, 2, 3, 4, 5, 6], 1.66237699; "increasing")]
+    fn entropy(unnorm_distr: &[usize], expected: f64) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        assert_float_eq!(naive_estimator.entropy(), expected, abs <= 1e-6);
+    }
+
+    #[test]
+    fn entropy_zero_bins() {
+        let with_zeros = NaiveEstimator::new(&[0, 3, 0, 2]).unwrap().entropy();
+        let without_zeros = NaiveEstimator::new(&[3, 2]).unwrap().entropy();
+        assert_float_eq!(with_zeros, without_zeros, abs <= 1e-12);
+    }
+
+    #[test]
+    fn entropy_bayesian_no_prior() {
+        let naive_estimator = NaiveEstimator::new(&[3, 1, 4, 1, 5]).unwrap();
+        assert_float_eq!(
+            naive_estimator.entropy_bayesian(0., 8),
+            naive_estimator.entropy(),
+            abs <= 1e-12
+        );
+    }
+
+    #[test]
+    fn entropy_bayesian_shrinks() {
+        let naive_estimator = NaiveEstimator::new(&[10, 1]).unwrap();
+        let support_size = 4;
+        let distances: Vec<f64> = [0., 0.5, 1., 10., 1000.]
+            .iter()
+            .map(|&prior| {
+                let estimation = naive_estimator.entropy_bayesian(prior, support_size);
+                (estimation - (support_size as f64).ln()).abs()
+            })
+            .collect();
+
+        assert!(distances.windows(2).all(|pair| pair[1] < pair[0]));
+        assert_float_eq!(distances[4], 0., abs <= 1e-4);
+    }
+
+    #[test]
+    fn support_size_and_total_count() {
+        let naive_estimator = NaiveEstimator::new(&[0, 3, 0, 2]).unwrap();
+        assert_eq!(naive_estimator.support_size(), 2);
+        assert_eq!(naive_estimator.total_count(), Some(5));
+
+        let naive_estimator = NaiveEstimator::new(&[usize::MAX, 1]).unwrap();
+        assert_eq!(naive_estimator.total_count(), None);
+    }
+
+    #[test]
+    fn owned() {
+        fn make() -> OwnedNaiveEstimator {
+            let samples: Vec<usize> = (0..30).map(|i| i % 4 + i % 3).collect();
+            OwnedNaiveEstimator::new(crate::count_dup(&samples)).unwrap()
+        }
+        let owned = make();
+        let expected = NaiveEstimator::new(owned.unnorm_distr()).unwrap().entropy();
+
+        assert_float_eq!(owned.entropy(), expected, abs <= 1e-12);
+        assert!(OwnedNaiveEstimator::<usize>::new(vec![0, 0]).is_err());
+    }
+
+    #[test_case(&[1; 8]; "uniform_eight")]
+    #[test_case(&[1, 2, 3, 4, 5, 6]; "increasing")]
+    #[test_case(&[9700, 100, 10, 1]; "skewed")]
+    fn with_float(unnorm_distr: &[usize]) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        let expected = naive_estimator.entropy();
+        let value = naive_estimator.with_float::<f32>().entropy();
+        assert_float_eq!(value as f64, expected, abs <= 1e-4);
+    }
+
+    #[test_case(&[7]; "one_element")]
+    #[test_case(&[5; 10]; "uniform")]
+    fn entropy_variance_zero(unnorm_distr: &[usize]) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        assert_float_eq!(naive_estimator.entropy_variance(), 0., abs <= 1e-12);
+    }
+
+    /// Same order of magnitude as the variance of naive entropies of random samples
+    #[test]
+    fn entropy_variance() {
+        use rand::Rng;
+
+        let unnorm_distr = [20, 40, 60, 80];
+        let all: usize = unnorm_distr.iter().sum();
+        let reps = 2000;
+        let mut rng = crate::test::rng(1);
+        let values: Vec<f64> = (0..reps)
+            .map(|_| {
+                let mut counts = [0; 4];
+                for _ in 0..all {
+                    let mut target = rng.gen_range(0..all);
+                    let index = unnorm_distr
+                        .iter()
+                        .position(|&count| {
+                            if target < count {
+                                true
+                            } else {
+                                target -= count;
+                                false
+                            }
+                        })
+                        .unwrap();
+                    counts[index] += 1;
+                }
+                NaiveEstimator::new(&counts).unwrap().entropy()
+            })
+            .collect();
+        let mean = values.iter().sum::<f64>() / reps as f64;
+        let empirical = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / reps as f64;
+
+        let ratio = NaiveEstimator::new(&unnorm_distr)
+            .unwrap()
+            .entropy_variance()
+            / empirical;
+        assert!(0.5 < ratio && ratio < 2.);
+    }
+
+    #[test]
+    fn entropy_with_base() {
+        let naive_estimator = NaiveEstimator::new(&[1; 8]).unwrap();
+        assert_float_eq!(
+            naive_estimator.entropy_with_base(LogBase::Bits),
+            3.,
+            abs <= 1e-12
+        );
+    }
+
+    #[test]
+    fn perplexity() {
+        let naive_estimator = NaiveEstimator::new(&[3; 8]).unwrap();
+        assert_float_eq!(naive_estimator.perplexity(), 8., abs <= 1e-12);
+    }
+
+    #[test_case(&[1; 8], 1.; "uniform")]
+    #[test_case(&[0, 5, 5, 0], 1.; "uniform_with_zeros")]
+    #[test_case(&[7], 0.; "one_element")]
+    #[test_case(&[1, 3], (4_f64.ln() - 0.75 * 3_f64.ln()) / 2_f64.ln(); "skewed")]
+    fn normalized_entropy(unnorm_distr: &[usize], expected: f64) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        assert_float_eq!(naive_estimator.normalized_entropy(), expected, abs <= 1e-12);
+    }
+
+    #[test_case(&[11], 0.; "uniform_one")]
+    #[test_case(&[1; 4], 4.0_f64.ln() + 3. / 8.; "uniform_four")]
+    #[test_case(&[1, 2, 3, 4, 5, 6], 1.66237699 + 5. / 42.; "increasing")]
+    fn entropy_miller_madow(unnorm_distr: &[usize], expected: f64) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        assert_float_eq!(
+            naive_estimator.entropy_miller_madow(),
+            expected,
+            abs <= 1e-6
+        );
+    }
+
+    #[test_case(&[1; 8], 8.0_f64.ln(); "uniform_eight")]
+    #[test_case(&[1, 2, 3, 4, 5, 6], 1.57818537; "increasing")]
+    #[test_case(&[97, 1, 1, 1], 0.06059962; "skewed")]
+    fn collision_entropy(unnorm_distr: &[usize], expected: f64) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        assert_float_eq!(naive_estimator.collision_entropy(), expected, abs <= 1e-6);
+        assert!(naive_estimator.collision_entropy() <= naive_estimator.entropy() + 1e-12);
+    }
+
+    #[test_case(&[11], 0.; "uniform_one")]
+    #[test_case(&[1; 4], 4.0_f64.ln(); "uniform_four")]
+    #[test_case(&[1, 2, 3, 4, 5, 6], 3.5_f64.ln(); "increasing")]
+    fn min_entropy(unnorm_distr: &[usize], expected: f64) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        assert_float_eq!(naive_estimator.min_entropy(), expected, abs <= 1e-12);
+        assert!(naive_estimator.min_entropy() <= naive_estimator.entropy() + 1e-12);
+    }
+
+    #[test_case(0., 6.0_f64.ln(), 1e-8; "zero")]
+    #[test_case(1., 1.6623769592, 1e-8; "one")]
+    #[test_case(2., 1.5781853689, 1e-8; "two")]
+    #[test_case(f64::INFINITY, 3.5_f64.ln(), 1e-8; "infinity")]
+    #[test_case(1e6, 3.5_f64.ln(), 1e-5; "big")] // Off by a factor `alpha / (alpha - 1)`
+    fn renyi_entropy(alpha: f64, expected: f64, tolerance: f64) {
+        let naive_estimator = NaiveEstimator::new(&[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_float_eq!(
+            naive_estimator.renyi_entropy(alpha),
+            expected,
+            abs <= tolerance
+        );
+    }
+
+    #[test_case(&[1; 4]; "uniform_four")]
+    #[test_case(&[1, 2, 3, 4, 5, 6]; "increasing")]
+    fn entropy_grassberger(unnorm_distr: &[usize]) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        assert!(naive_estimator.entropy_grassberger() > naive_estimator.entropy());
+    }
+
+    #[test]
+    fn entropy_grassberger_value() {
+        // G(1) = ψ(1) - (ψ(1) - ψ(1/2)) / 2 = ψ(1) / 2 + ψ(1/2) / 2
+        let g = (-0.5772156649 - 1.9635100260) / 2.;
+        let naive_estimator = NaiveEstimator::new(&[1; 4]).unwrap();
+        assert_float_eq!(
+            naive_estimator.entropy_grassberger(),
+            4.0_f64.ln() - g,
+            abs <= 1e-8
+        );
+    }
+
+    #[test_case(BiasCorrection::None, 4_f64.ln(); "none")]
+    #[test_case(BiasCorrection::MillerMadow, 4_f64.ln() + 3. / 8.; "miller_madow")]
+    #[test_case(BiasCorrection::Grassberger, 4_f64.ln() + (0.5772156649 + 1.9635100260) / 2.; "grassberger")]
+    fn entropy_corrected(method: BiasCorrection, expected: f64) {
+        let naive_estimator = NaiveEstimator::new(&[1, 1, 1, 1]).unwrap();
+        assert_float_eq!(
+            naive_estimator.entropy_corrected(method),
+            expected,
+            abs <= 1e-8
+        );
+    }
+
+    #[test]
+    fn frequency_spectrum() {
+        let naive_estimator = NaiveEstimator::new(&[1, 1, 2, 3, 3, 3, 0]).unwrap();
+        let spectrum: Vec<(usize, usize)> =
+            naive_estimator.frequency_spectrum().into_iter().collect();
+        assert_eq!(spectrum, vec![(1, 2), (2, 1), (3, 3)]);
+    }
+
+    #[test_case(&[1, 1, 1, 2, 3], 3. / 8.; "three_singletons")]
+    #[test_case(&[0, 2, 3], 0.; "no_singletons")]
+    #[test_case(&[1, 1, 1, 1], 1.; "only_singletons")]
+    fn unseen_mass(unnorm_distr: &[usize], expected: f64) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        assert_float_eq!(naive_estimator.unseen_mass(), expected, abs <= 1e-12);
+    }
+
+    #[test_case(&[1, 2, 3, 4, 5, 6]; "increasing")]
+    #[test_case(&[1, 1, 5]; "singletons")]
+    #[test_case(&[9]; "one_element")]
+    fn entropy_jackknife(unnorm_distr: &[usize]) {
+        // Leave-one-out estimations computed by actually removing a sample
+        let all: usize = unnorm_distr.iter().sum();
+        let sum_leave_one_out: f64 = (0..unnorm_distr.len())
+            .filter(|&i| unnorm_distr[i] > 0)
+            .map(|i| {
+                let mut leave_one_out = unnorm_distr.to_vec();
+                leave_one_out[i] -= 1;
+                leave_one_out.retain(|&count| count > 0);
+                let entropy = NaiveEstimator::new(&leave_one_out).unwrap().entropy();
+                unnorm_distr[i] as f64 * entropy
+            })
+            .sum();
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        let expected = all as f64 * naive_estimator.entropy()
+            - (all - 1) as f64 / all as f64 * sum_leave_one_out;
+
+        assert_float_eq!(naive_estimator.entropy_jackknife(), expected, abs <= 1e-9);
+    }
+
+    #[test]
+    fn entropy_jackknife_increasing() {
+        let naive_estimator = NaiveEstimator::new(&[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_float_eq!(naive_estimator.entropy_jackknife(), 1.8189523, abs <= 1e-6);
+        assert!(naive_estimator.entropy_jackknife() > naive_estimator.entropy());
+    }
+
+    #[test_case(&[1, 1, 1, 2, 3, 5], 1.9275250369567727; "several_singletons")]
+    #[test_case(&[1, 1, 1], 2.460486508261295; "all_singletons")]
+    #[test_case(&[0, 7], 0.; "one_element")]
+    fn entropy_chao_shen(unnorm_distr: &[usize], expected: f64) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        assert_float_eq!(naive_estimator.entropy_chao_shen(), expected, abs <= 1e-12);
+    }
+
+    #[test_case(&[1; 4], 2.0279506082668113; "uniform_four")]
+    #[test_case(&[1, 2, 3, 4, 5, 6], 1.791325222493556; "increasing")]
+    fn entropy_horvitz_thompson(unnorm_distr: &[usize], expected: f64) {
+        let naive_estimator = NaiveEstimator::new(unnorm_distr).unwrap();
+        let value = naive_estimator.entropy_horvitz_thompson();
+        assert_float_eq!(value, expected, abs <= 1e-12);
+        assert!(value > naive_estimator.entropy());
+    }
+
+    #[test_case(&[11]; "uniform_one")]
+    #[test_case(&[1; 4]; "uniform_four")]
+    #[test_case(&[1, 2, 3, 4, 5, 6]; "increasing")]
+    fn float_counts(unnorm_distr: &[usize]) {
+        let float_unnorm_distr: Vec<f64> = unnorm_distr.iter().map(|x| *x as f64).collect();
+        let expected = NaiveEstimator::new(unnorm_distr).unwrap().entropy();
+        let naive_estimator = NaiveEstimator::new(&float_unnorm_distr).unwrap();
+        assert_float_eq!(naive_estimator.entropy(), expected, abs <= 1e-12);
+    }
+
+    #[test]
+    fn from_weights() {
+        let expected = NaiveEstimator::new(&[1, 2, 3]).unwrap().entropy();
+        let naive_estimator = NaiveEstimator::from_weights(&[1., 2., 3.]).unwrap();
+        assert_float_eq!(naive_estimator.entropy(), expected, abs <= 1e-12);
+        assert_float_eq!(
+            weighted_entropy(&[0.1, 0.2, 0.3]).unwrap(),
+            expected,
+            abs <= 1e-12
+        );
+    }
+
+    #[test_case(&[1., -2., 3.]; "negative")]
+    #[test_case(&[1., f64::NAN, 3.]; "nan")]
+    #[test_case(&[1., f64::INFINITY]; "infinite")]
+    #[test_case(&[0., 0.]; "null")]
+    fn from_weights_invalid(weights: &[f64]) {
+        assert!(NaiveEstimator::from_weights(weights).is_err());
+    }
+
+    #[test]
+    fn wide_counts() {
+        let unnorm_distr: [u64; 3] = [1 << 62, 1 << 62, 1 << 63];
+        let value = entropy_from_u64(&unnorm_distr).unwrap();
+        assert_float_eq!(value, 1.5 * 2_f64.ln(), abs <= 1e-12);
+    }
+
+    #[test]
+    fn huge_counts() {
+        let unnorm_distr = [usize::MAX, usize::MAX];
+        let naive_estimator = NaiveEstimator::new(&unnorm_distr).unwrap();
+        assert_float_eq!(
+            naive_estimator.entropy_grassberger(),
+            2_f64.ln(),
+            abs <= 1e-12
+        );
+        let value = entropy_with_rare_bucket(&[usize::MAX, 1, usize::MAX, 1], 2).unwrap();
+        assert_float_eq!(value, 2_f64.ln(), abs <= 1e-12);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn from_ndarray() {
+        let unnorm_distr = [1, 2, 3, 4, 5, 6];
+        let array = ndarray::Array1::from(unnorm_distr.to_vec());
+        let expected = NaiveEstimator::new(&unnorm_distr).unwrap().entropy();
+        assert_float_eq!(
+            NaiveEstimator::from(array.view()).entropy(),
+            expected,
+            abs <= 1e-12
+        );
+    }
+
+    #[test]
+    fn rare_bucket_min_count_one() {
+        let unnorm_distr = [1, 2, 3, 4, 5, 6];
+        let expected = NaiveEstimator::new(&unnorm_distr).unwrap().entropy();
+        let value = entropy_with_rare_bucket(&unnorm_distr, 1).unwrap();
+        assert_float_eq!(value, expected, abs <= 1e-12);
+    }
+
+    #[test]
+    fn rare_bucket_monotone() {
+        let unnorm_distr = [1, 1, 2, 3, 1, 5, 8, 2, 13, 1];
+        let values: Vec<f64> = (1..=14)
+            .map(|min_count| entropy_with_rare_bucket(&unnorm_distr, min_count).unwrap())
+            .collect();
+
+        for window in values.windows(2) {
+            assert!(window[1] <= window[0] + 1e-12);
+        }
+        assert!(values[values.len() - 1] < values[0]);
+        assert_float_eq!(values[values.len() - 1], 0., abs <= 1e-12);
+    }
+
+    #[test]
+    fn rare_bucket_null() {
+        assert!(entropy_with_rare_bucket(&[0, 0], 2).is_err());
+    }
+}
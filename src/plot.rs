@@ -0,0 +1,121 @@
+use plotters::prelude::*;
+use std::{iter, path::Path};
+use thiserror::Error;
+
+use crate::{Estimator, FittingError, SamplingMethod};
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+const CURVE_POINTS: usize = 200;
+
+#[derive(Error, Debug)]
+pub enum PlotError {
+    #[error(transparent)]
+    Fitting(#[from] FittingError),
+    #[error("Failed to draw the plot: {0}")]
+    Drawing(String),
+}
+
+/// Draws the extrapolation of `estimator` to a PNG file at `path`.
+///
+/// The plot shows the naive entropy estimations as points `(1/n, value)`,
+/// the fitted polynomial in `1/n` and, at `1/n = 0`, the extrapolated entropy.
+/// No external program is needed, and there is no text in the plot.
+///
+/// # Remarks
+///
+/// Naive entropy estimations are computed again, as in [`entropy_fit`].
+///
+/// # Errors
+///
+/// If there are numerical instabilities in the fit, or if drawing fails
+/// (for example, if the file can not be written).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use approx_entropy::{plot_fit, Estimator};
+/// let unnorm_distr = [1, 2, 3, 4, 5, 6];
+/// let mut estimator = Estimator::from(unnorm_distr);
+/// plot_fit(&mut estimator, std::path::Path::new("fit.png")).unwrap();
+/// ```
+///
+/// [`entropy_fit`]: struct.Estimator.html#method.entropy_fit
+pub fn plot_fit<M>(estimator: &mut Estimator<M>, path: &Path) -> Result<(), PlotError>
+where
+    M: SamplingMethod,
+{
+    let fit = estimator.entropy_fit()?;
+    let points: Vec<(f64, f64)> = fit
+        .naive_entropies
+        .iter()
+        .map(|&(size, value)| (1. / size as f64, value))
+        .collect();
+    let polynomial = |x: f64| {
+        fit.coefficients
+            .iter()
+            .rev()
+            .fold(0., |acc, coefficient| acc * x + coefficient)
+    };
+
+    let max_x = points.iter().map(|&(x, _)| x).fold(0., f64::max);
+    let curve: Vec<(f64, f64)> = (0..=CURVE_POINTS)
+        .map(|i| {
+            let x = max_x * i as f64 / CURVE_POINTS as f64;
+            (x, polynomial(x))
+        })
+        .collect();
+    let (min_y, max_y) = points.iter().chain(&curve).fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(min_y, max_y), &(_, y)| (min_y.min(y), max_y.max(y)),
+    );
+    let margin_y = ((max_y - min_y) * 0.05).max(1e-3);
+
+    let root = BitMapBackend::new(path, (WIDTH, HEIGHT)).into_drawing_area();
+    root.fill(&WHITE).map_err(drawing_error)?;
+    let mut chart = ChartBuilder::on(&root)
+        .margin(10)
+        .build_cartesian_2d(0. ..max_x * 1.05, min_y - margin_y..max_y + margin_y)
+        .map_err(drawing_error)?;
+    chart
+        .draw_series(LineSeries::new(curve, &RED))
+        .map_err(drawing_error)?;
+    chart
+        .draw_series(
+            points
+                .into_iter()
+                .map(|point| Circle::new(point, 3, BLUE.filled())),
+        )
+        .map_err(drawing_error)?;
+    chart
+        .draw_series(iter::once(Cross::new((0., fit.estimate), 6, BLACK)))
+        .map_err(drawing_error)?;
+    root.present().map_err(drawing_error)?;
+    Ok(())
+}
+
+fn drawing_error<E>(error: E) -> PlotError
+where
+    E: std::error::Error,
+{
+    PlotError::Drawing(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{test::rng, Bootstrap};
+
+    #[test]
+    fn smoke() {
+        let path = std::env::temp_dir().join("approx_entropy_plot_fit_smoke.png");
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut estimator = Estimator::new(bootstrap);
+        plot_fit(&mut estimator, &path).unwrap();
+
+        let len = std::fs::metadata(&path).unwrap().len();
+        std::fs::remove_file(&path).unwrap();
+        assert!(len > 0);
+    }
+}
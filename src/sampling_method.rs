@@ -1,5 +1,32 @@
 mod bootstrap;
 mod fixed_partition;
+mod weighted_bootstrap;
 
-pub use bootstrap::Bootstrap;
+pub use bootstrap::{
+    Bootstrap, BootstrapBuilder, ConstructionError, ScheduleError, TooManySamples,
+};
 pub use fixed_partition::FixedPartition;
+pub use weighted_bootstrap::WeightedBootstrap;
+
+use core::fmt;
+
+use crate::SamplingMethod;
+
+/// Writes a one-line summary of the configuration of `sampling_method`,
+/// shared by the `Display` implementations of sampling methods.
+fn fmt_summary<M>(name: &str, sampling_method: &M, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    M: SamplingMethod,
+{
+    write!(
+        f,
+        "{}(num_groups: {}, degree: {}, dataset_size: {}, total_samples: {}, size_subsamples: {:?}, samples_rep: {:?})",
+        name,
+        sampling_method.num_groups(),
+        sampling_method.degree(),
+        sampling_method.dataset_size(),
+        sampling_method.total_samples(),
+        sampling_method.size_subsamples(),
+        sampling_method.samples_rep(),
+    )
+}
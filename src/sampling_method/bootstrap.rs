@@ -1,13 +1,46 @@
-use rand::{seq::SliceRandom, Rng};
+use core::{convert::TryFrom, fmt, hash::Hash};
+#[cfg(feature = "ndarray")]
+use ndarray::ArrayView1;
+use rand::{rngs::ThreadRng, Rng, SeedableRng};
+use rand_pcg::Pcg32;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{traits::SamplingMethod, utils::count_dup, NaiveEstimator};
+use crate::{
+    estimator::{DEFAULT_DEGREE, DEFAULT_NUM_GROUPS},
+    traits::{Reseed, SamplingMethod},
+    utils::{checked_total, count_dup_ordered},
+    NaiveEstimator,
+};
+
+const AUTO_MIN_NUM_GROUPS: usize = 2;
+const AUTO_MAX_NUM_GROUPS: usize = 6;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "BootstrapData", bound(deserialize = "R: Rng + Default"))
+)]
 pub struct Bootstrap<R> {
     num_groups: usize,
     degree: usize,
     unnorm_distr: Vec<usize>,
+    /// Whether subsamples are drawn with replacement.
+    replacement: bool,
+    /// Custom size of the subsamples of each group, replacing the geometric schedule.
+    size_schedule: Option<Vec<usize>>,
+    /// Custom number of repetitions of each group, replacing the schedule `4^i`.
+    rep_schedule: Option<Vec<usize>>,
+    /// Cumulative counts of the unnormalized distribution, to draw subsamples.
+    /// Built on first use and cleared when the unnormalized distribution changes.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bin_counts: Option<BinCounts>,
+    /// Not serialized, a default one is used on deserialization.
+    #[cfg_attr(feature = "serde", serde(skip))]
     rng: R,
 }
 
@@ -17,8 +50,84 @@ pub enum ConstructionError {
         "Failed construction. There are too few samples (or the number of groups is too big)."
     )]
     TooFewSamples(#[from] TooFewSamples),
+    #[error("Failed construction. The total number of samples overflows.")]
+    TooManySamples(#[from] TooManySamples),
     #[error("Failed construction. There are too few number of groups (or the degree is too big).")]
     LowNumGroups(#[from] LowNumGroups),
+    #[error("Failed construction. There are too many groups.")]
+    TooManyGroups(#[from] TooManyGroups),
+}
+
+/// Serialized fields of a [`Bootstrap`], validated on deserialization.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct BootstrapData {
+    num_groups: usize,
+    degree: usize,
+    unnorm_distr: Vec<usize>,
+    #[serde(default)]
+    replacement: bool,
+    #[serde(default)]
+    size_schedule: Option<Vec<usize>>,
+    #[serde(default)]
+    rep_schedule: Option<Vec<usize>>,
+}
+
+#[cfg(feature = "serde")]
+impl<R> TryFrom<BootstrapData> for Bootstrap<R>
+where
+    R: Rng + Default,
+{
+    type Error = DeserializeError;
+
+    fn try_from(data: BootstrapData) -> Result<Self, Self::Error> {
+        let bootstrap = match data.size_schedule {
+            Some(sizes) => {
+                checked_total(&data.unnorm_distr)
+                    .ok_or(ConstructionError::TooManySamples(TooManySamples))?;
+                Bootstrap::new_unchecked(&data.unnorm_distr, 0, data.degree, R::default())
+                    .with_size_schedule(sizes)?
+            }
+            None => Bootstrap::new(
+                &data.unnorm_distr,
+                data.num_groups,
+                data.degree,
+                R::default(),
+            )?,
+        };
+        let bootstrap = match data.rep_schedule {
+            Some(reps) => bootstrap.with_rep_schedule(reps)?,
+            None => bootstrap,
+        };
+        Ok(bootstrap.with_replacement(data.replacement))
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Error, Debug)]
+pub enum DeserializeError {
+    #[error(transparent)]
+    Construction(#[from] ConstructionError),
+    #[error(transparent)]
+    Schedule(#[from] ScheduleError),
+}
+
+#[derive(Error, Debug)]
+pub enum ScheduleError {
+    #[error("Invalid schedule: subsample size {size} is not between 1 and the {available} available samples.")]
+    SizeOutOfRange { size: usize, available: usize },
+    #[error("Invalid schedule: subsample size {size} is given more than once.")]
+    DuplicateSize { size: usize },
+    #[error("Invalid schedule: there are too few groups (or the degree is too big).")]
+    LowNumGroups(#[from] LowNumGroups),
+    #[error("Invalid schedule: there are too many groups.")]
+    TooManyGroups(#[from] TooManyGroups),
+    #[error("Invalid schedule: {found} repetitions were given for {expected} groups.")]
+    LengthMismatch { expected: usize, found: usize },
+    #[error("Invalid schedule: every group needs at least one repetition.")]
+    ZeroRepetitions,
+    #[error("Invalid schedule: the total number of repetitions overflows.")]
+    RepetitionsOverflow,
 }
 
 impl<R> Bootstrap<R>
@@ -35,7 +144,8 @@ where
     /// # Errors
     ///
     /// If the number of groups is less or equal than the degree;
-    /// or if the total number of available samples is too low (for the desired number of groups).
+    /// if the total number of available samples is too low (for the desired number of groups);
+    /// or if the total number of samples or of repetitions does not fit in a `usize`.
     ///
     /// [`count_dup`]: fn.count_dup.html
     pub fn new(
@@ -45,8 +155,9 @@ where
         rng: R,
     ) -> Result<Self, ConstructionError> {
         if num_groups > degree {
-            let available_samples: usize = unnorm_distr.iter().sum();
-            if available_samples >= 1 << num_groups {
+            let available_samples = checked_total(unnorm_distr).ok_or(TooManySamples)?;
+            if enough_samples(available_samples, num_groups) {
+                check_samples_rep(num_groups)?;
                 Ok(Bootstrap::new_unchecked(
                     unnorm_distr,
                     num_groups,
@@ -61,15 +172,477 @@ where
         }
     }
 
+    /// Construct a new `Bootstrap` from samples, instead of an unnormalized distribution.
+    ///
+    /// Samples are counted once, and only the counts are stored.
+    /// Counts are in order of first appearance in `samples`,
+    /// so that estimations are reproducible with a seeded random number generator.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Bootstrap::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::{Bootstrap, SamplingMethod};
+    /// let samples = ["a", "b", "a", "c", "b", "a", "d", "a"];
+    /// let bootstrap = Bootstrap::from_samples(&samples, 3, 2, rand::thread_rng()).unwrap();
+    /// assert_eq!(bootstrap.pooled_unnorm_distr(), vec![4, 2, 1, 1]);
+    /// ```
+    ///
+    /// [`Bootstrap::new`]: struct.Bootstrap.html#method.new
+    pub fn from_samples<T>(
+        samples: &[T],
+        num_groups: usize,
+        degree: usize,
+        rng: R,
+    ) -> Result<Self, ConstructionError>
+    where
+        T: Hash + Eq,
+    {
+        Bootstrap::new(&count_dup_ordered(samples), num_groups, degree, rng)
+    }
+
+    /// Construct a new `Bootstrap` from an unnormalized distribution stored in an `ndarray`.
+    ///
+    /// Contiguous arrays are read in place, others are copied first.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Bootstrap::new`].
+    ///
+    /// [`Bootstrap::new`]: struct.Bootstrap.html#method.new
+    #[cfg(feature = "ndarray")]
+    pub fn from_ndarray(
+        unnorm_distr: ArrayView1<usize>,
+        num_groups: usize,
+        degree: usize,
+        rng: R,
+    ) -> Result<Self, ConstructionError> {
+        match unnorm_distr.as_slice() {
+            Some(unnorm_distr) => Bootstrap::new(unnorm_distr, num_groups, degree, rng),
+            None => Bootstrap::new(&unnorm_distr.to_vec(), num_groups, degree, rng),
+        }
+    }
+
+    /// Construct a new `Bootstrap`, choosing the number of groups and the degree
+    /// from the total number of samples `N`.
+    ///
+    /// The number of groups is `floor(log2(N)) - 1`, clamped between 2 and 6,
+    /// and the degree is one less than the number of groups.
+    ///
+    /// # Remarks
+    ///
+    /// The number of subsamples grows as `4^{num_groups}`,
+    /// hence the upper bound on the number of groups.
+    ///
+    /// # Errors
+    ///
+    /// If there are fewer than 4 samples,
+    /// or if the total number of samples does not fit in a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::{Bootstrap, SamplingMethod};
+    /// let bootstrap = Bootstrap::auto(&[1, 2, 3, 4, 5, 6], rand::thread_rng()).unwrap();
+    /// assert_eq!(bootstrap.num_groups(), 3);
+    /// assert_eq!(bootstrap.degree(), 2);
+    /// ```
+    pub fn auto(unnorm_distr: &[usize], rng: R) -> Result<Self, ConstructionError> {
+        let available_samples = checked_total(unnorm_distr).ok_or(TooManySamples)?;
+        let num_groups = auto_num_groups(available_samples);
+        Bootstrap::new(unnorm_distr, num_groups, num_groups - 1, rng)
+    }
+
     /// Construct a new `Bootstrap`.
+    ///
+    /// The total number of samples should fit in a `usize`:
+    /// otherwise, it saturates to `usize::MAX` when computing the size of the subsamples.
     pub fn new_unchecked(unnorm_distr: &[usize], num_groups: usize, degree: usize, rng: R) -> Self {
         Bootstrap {
             num_groups,
             degree,
             unnorm_distr: unnorm_distr.to_vec(),
+            replacement: false,
+            size_schedule: None,
+            rep_schedule: None,
+            bin_counts: None,
+            rng,
+        }
+    }
+
+    /// Sets whether subsamples are drawn with replacement.
+    ///
+    /// By default, subsamples are drawn without replacement:
+    /// each available sample appears at most once in a subsample,
+    /// so that the largest subsample is exactly the pooled unnormalized distribution.
+    /// With replacement, as in the classic statistical bootstrap,
+    /// each element of a subsample is drawn independently from all available samples,
+    /// so the same sample can appear several times.
+    pub fn with_replacement(mut self, replacement: bool) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Uses `sizes` as the size of the subsamples, one group per size,
+    /// instead of the default geometric schedule `N, N/2, N/4, ...`.
+    ///
+    /// The number of groups becomes the number of sizes,
+    /// and sizes are sorted from greatest to smallest, see [`size_subsamples`].
+    /// Any custom repetition schedule is dropped, see [`with_rep_schedule`].
+    /// Changing the number of groups afterwards goes back to the geometric schedule.
+    ///
+    /// # Errors
+    ///
+    /// If a size is zero or greater than the total number of samples;
+    /// if a size is given more than once;
+    /// if there are not more sizes than the degree;
+    /// or if the total number of repetitions does not fit in a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// Linearly spaced sizes.
+    /// ```
+    /// # use approx_entropy::{Bootstrap, SamplingMethod};
+    /// let bootstrap = Bootstrap::new(&[10, 20, 30, 40], 3, 2, rand::thread_rng())
+    ///     .unwrap()
+    ///     .with_size_schedule(vec![25, 50, 75, 100])
+    ///     .unwrap();
+    /// assert_eq!(bootstrap.size_subsamples(), vec![100, 75, 50, 25]);
+    /// ```
+    ///
+    /// [`size_subsamples`]: trait.SamplingMethod.html#tymethod.size_subsamples
+    /// [`with_rep_schedule`]: #method.with_rep_schedule
+    pub fn with_size_schedule(mut self, mut sizes: Vec<usize>) -> Result<Self, ScheduleError> {
+        let available = self.available_samples();
+        if let Some(&size) = sizes.iter().find(|&&size| size == 0 || size > available) {
+            Err(ScheduleError::SizeOutOfRange { size, available })?
+        }
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        if let Some(pair) = sizes.windows(2).find(|pair| pair[0] == pair[1]) {
+            Err(ScheduleError::DuplicateSize { size: pair[0] })?
+        }
+        if sizes.len() <= self.degree {
+            Err(LowNumGroups)?
+        }
+        check_samples_rep(sizes.len())?;
+
+        self.num_groups = sizes.len();
+        self.size_schedule = Some(sizes);
+        self.rep_schedule = None;
+        Ok(self)
+    }
+
+    /// Uses `reps` as the number of repetitions of each group,
+    /// instead of the default schedule `1, 4, 16, ...`.
+    ///
+    /// Repetitions are given in the same order as the groups, see [`samples_rep`]:
+    /// the first entry is for the greatest subsamples.
+    /// Changing the number of groups (or the size schedule) afterwards
+    /// goes back to the default schedule.
+    ///
+    /// # Remarks
+    ///
+    /// Every naive entropy estimation is one point of the least squares fit,
+    /// so a group with `r` repetitions weighs as much as `r` points at its size.
+    /// Small subsamples have a higher variance, which is why they get
+    /// more repetitions by default.
+    ///
+    /// # Errors
+    ///
+    /// If there is not one entry per group, if an entry is zero,
+    /// or if the total number of repetitions does not fit in a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::{Bootstrap, SamplingMethod};
+    /// let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rand::thread_rng())
+    ///     .unwrap()
+    ///     .with_rep_schedule(vec![2, 8, 10])
+    ///     .unwrap();
+    /// assert_eq!(bootstrap.total_samples(), 20);
+    /// ```
+    ///
+    /// [`samples_rep`]: trait.SamplingMethod.html#tymethod.samples_rep
+    pub fn with_rep_schedule(mut self, reps: Vec<usize>) -> Result<Self, ScheduleError> {
+        if reps.len() != self.num_groups {
+            Err(ScheduleError::LengthMismatch {
+                expected: self.num_groups,
+                found: reps.len(),
+            })?
+        }
+        if reps.contains(&0) {
+            Err(ScheduleError::ZeroRepetitions)?
+        }
+        reps.iter()
+            .try_fold(0_usize, |total, &rep| total.checked_add(rep))
+            .ok_or(ScheduleError::RepetitionsOverflow)?;
+
+        self.rep_schedule = Some(reps);
+        Ok(self)
+    }
+
+    /// Fraction of subsamples made only of distinct elements (singletons),
+    /// among all subsamples drawn to compute naive entropies.
+    ///
+    /// The naive entropy of such a subsample is maximal,
+    /// so a high fraction warns that the extrapolation is dominated by undersampling.
+    ///
+    /// # Remarks
+    ///
+    /// New subsamples are drawn, advancing the random number generator.
+    pub fn singleton_fraction(&mut self) -> f64 {
+        let mut num_subsamples = 0;
+        let mut num_singleton_subsamples = 0;
+        self.for_each_subsample(|group_size, unnorm_distr| {
+            num_subsamples += 1;
+            if unnorm_distr.len() == group_size {
+                num_singleton_subsamples += 1;
+            }
+        });
+        num_singleton_subsamples as f64 / num_subsamples as f64
+    }
+
+    /// Draws all subsamples and calls `f` with the size of each subsample
+    /// and its unnormalized distribution.
+    fn for_each_subsample<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &[usize]),
+    {
+        let bin_counts = self.take_bin_counts();
+
+        let samples_rep = self.samples_rep();
+        for (group_index, group_size) in self.size_subsamples().iter().enumerate() {
+            for _ in 0..samples_rep[group_index] {
+                let seed = self.rng.gen::<u64>();
+                let unnorm_distr =
+                    draw_seeded_subsample(&bin_counts, *group_size, self.replacement, seed);
+                f(*group_size, &unnorm_distr);
+            }
+        }
+
+        self.bin_counts = Some(bin_counts);
+    }
+
+    /// Total number of samples, saturating at `usize::MAX`.
+    ///
+    /// It only saturates when constructed with [`new_unchecked`],
+    /// every other constructor checks for overflow.
+    ///
+    /// [`new_unchecked`]: #method.new_unchecked
+    fn available_samples(&self) -> usize {
+        checked_total(&self.unnorm_distr).unwrap_or(usize::MAX)
+    }
+
+    /// Takes the cached cumulative counts, building them if needed.
+    ///
+    /// The caller is expected to put them back once done.
+    fn take_bin_counts(&mut self) -> BinCounts {
+        match self.bin_counts.take() {
+            Some(bin_counts) => bin_counts,
+            None => BinCounts::new(&self.unnorm_distr),
+        }
+    }
+
+    /// Parallel version of [`naive_entropies`], computing naive entropies
+    /// in the global thread pool of rayon.
+    ///
+    /// As in [`naive_entropies`], a seed is drawn from the random number generator
+    /// for each subsample, in order, and the subsample is drawn with its own generator
+    /// seeded from it. Therefore, the result does not depend on the number of threads
+    /// and is the same as the one of [`naive_entropies`].
+    ///
+    /// [`naive_entropies`]: trait.SamplingMethod.html#tymethod.naive_entropies
+    #[cfg(feature = "rayon")]
+    pub fn naive_entropies_par(&mut self) -> Vec<(usize, f64)> {
+        let bin_counts = self.take_bin_counts();
+        let replacement = self.replacement;
+
+        let samples_rep = self.samples_rep();
+        let mut tasks = Vec::with_capacity(self.total_samples());
+        for (group_index, group_size) in self.size_subsamples().into_iter().enumerate() {
+            for _ in 0..samples_rep[group_index] {
+                tasks.push((group_size, self.rng.gen::<u64>()));
+            }
+        }
+
+        let naive_entropies = tasks
+            .into_par_iter()
+            .map(|(group_size, seed)| {
+                let unnorm_distr =
+                    draw_seeded_subsample(&bin_counts, group_size, replacement, seed);
+                // Never fails because group_size is never null.
+                let naive_entropy_value = NaiveEstimator::new_unchecked(&unnorm_distr).entropy();
+                (group_size, naive_entropy_value)
+            })
+            .collect();
+
+        self.bin_counts = Some(bin_counts);
+        naive_entropies
+    }
+}
+
+impl Bootstrap<ThreadRng> {
+    /// Returns a builder to configure a new `Bootstrap`.
+    ///
+    /// See [`BootstrapBuilder`] for the defaults.
+    ///
+    /// [`BootstrapBuilder`]: struct.BootstrapBuilder.html
+    pub fn builder() -> BootstrapBuilder<ThreadRng> {
+        BootstrapBuilder::default()
+    }
+}
+
+/// Builder of [`Bootstrap`].
+///
+/// When not set, the number of groups is 3, the degree is 2,
+/// the random number generator is `rand::thread_rng()`
+/// and the unnormalized distribution is empty.
+///
+/// # Examples
+///
+/// Reusing the configuration for several distributions.
+/// ```
+/// # use approx_entropy::Bootstrap;
+/// let builder = Bootstrap::builder().num_groups(4).degree(1);
+/// for unnorm_distr in [[10, 20, 30], [5, 15, 25]] {
+///     let bootstrap = builder.clone().unnorm_distr(&unnorm_distr).build().unwrap();
+/// }
+/// ```
+///
+/// [`Bootstrap`]: struct.Bootstrap.html
+#[derive(Debug, Clone)]
+pub struct BootstrapBuilder<R> {
+    num_groups: usize,
+    degree: usize,
+    unnorm_distr: Vec<usize>,
+    rng: R,
+}
+
+impl Default for BootstrapBuilder<ThreadRng> {
+    fn default() -> Self {
+        BootstrapBuilder {
+            num_groups: DEFAULT_NUM_GROUPS,
+            degree: DEFAULT_DEGREE,
+            unnorm_distr: Vec::new(),
+            rng: rand::thread_rng(),
+        }
+    }
+}
+
+impl<R> BootstrapBuilder<R>
+where
+    R: Rng,
+{
+    /// Sets the number of groups.
+    pub fn num_groups(mut self, num_groups: usize) -> Self {
+        self.num_groups = num_groups;
+        self
+    }
+
+    /// Sets the degree of the polynomial used to fit the naive entropy estimations.
+    pub fn degree(mut self, degree: usize) -> Self {
+        self.degree = degree;
+        self
+    }
+
+    /// Sets the unnormalized distribution from which subsamples will be taken.
+    pub fn unnorm_distr(mut self, unnorm_distr: &[usize]) -> Self {
+        self.unnorm_distr = unnorm_distr.to_vec();
+        self
+    }
+
+    /// Sets the random number generator.
+    pub fn rng<R2>(self, rng: R2) -> BootstrapBuilder<R2>
+    where
+        R2: Rng,
+    {
+        BootstrapBuilder {
+            num_groups: self.num_groups,
+            degree: self.degree,
+            unnorm_distr: self.unnorm_distr,
             rng,
         }
     }
+
+    /// Constructs the `Bootstrap`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Bootstrap::new`]: if the number of groups is less or equal than the degree;
+    /// or if the total number of samples is less than `2^{num_groups}`.
+    ///
+    /// [`Bootstrap::new`]: struct.Bootstrap.html#method.new
+    pub fn build(self) -> Result<Bootstrap<R>, ConstructionError> {
+        Bootstrap::new(&self.unnorm_distr, self.num_groups, self.degree, self.rng)
+    }
+}
+
+impl<R> Bootstrap<R>
+where
+    R: SeedableRng,
+{
+    /// Returns a copy with the same configuration,
+    /// but with a new random number generator seeded with `seed`.
+    ///
+    /// Unlike cloning, which duplicates the state of the random number generator,
+    /// forks with different seeds give independent estimations.
+    pub fn fork(&self, seed: u64) -> Bootstrap<R> {
+        Bootstrap {
+            num_groups: self.num_groups,
+            degree: self.degree,
+            unnorm_distr: self.unnorm_distr.clone(),
+            replacement: self.replacement,
+            size_schedule: self.size_schedule.clone(),
+            rep_schedule: self.rep_schedule.clone(),
+            bin_counts: self.bin_counts.clone(),
+            rng: R::seed_from_u64(seed),
+        }
+    }
+}
+
+impl<R> Bootstrap<R>
+where
+    R: Rng + SeedableRng,
+{
+    /// Returns a copy with the same configuration, whose random number generator
+    /// is seeded from the one of `self`, advancing it.
+    ///
+    /// Children are independent of each other and of the parent,
+    /// but the whole tree of streams is determined by the seed of the parent.
+    /// This allows reproducible parallel sweeps: split once per task,
+    /// then run each child in its own thread.
+    ///
+    /// See also [`fork`] and [`Reseed`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::Bootstrap;
+    /// # use rand::SeedableRng;
+    /// let rng = rand_pcg::Pcg32::seed_from_u64(1);
+    /// let mut parent = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng).unwrap();
+    /// let children: Vec<_> = (0..4).map(|_| parent.split()).collect();
+    /// ```
+    ///
+    /// [`fork`]: #method.fork
+    /// [`Reseed`]: trait.Reseed.html
+    pub fn split(&mut self) -> Bootstrap<R> {
+        let seed = self.rng.gen();
+        self.fork(seed)
+    }
+}
+
+impl<R> Reseed for Bootstrap<R>
+where
+    R: SeedableRng,
+{
+    fn reseed(&mut self, seed: u64) {
+        self.rng = R::seed_from_u64(seed);
+    }
 }
 
 #[derive(Error, Debug)]
@@ -84,13 +657,72 @@ pub struct HighDegree;
 #[error("Invalid unnormalized distribution: the total number of samples is too low.")]
 pub struct TooFewSamples;
 
+#[derive(Error, Debug)]
+#[error("Invalid unnormalized distribution: the total number of samples overflows.")]
+pub struct TooManySamples;
+
+#[derive(Error, Debug)]
+#[error("Invalid number of groups: the total number of repetitions overflows.")]
+pub struct TooManyGroups;
+
+#[derive(Error, Debug)]
+pub enum NumGroupsError {
+    #[error(transparent)]
+    LowNumGroups(#[from] LowNumGroups),
+    #[error(transparent)]
+    TooFewSamples(#[from] TooFewSamples),
+    #[error(transparent)]
+    TooManyGroups(#[from] TooManyGroups),
+}
+
+#[derive(Error, Debug)]
+pub enum UnnormDistrError {
+    #[error(transparent)]
+    TooFewSamples(#[from] TooFewSamples),
+    #[error(transparent)]
+    TooManySamples(#[from] TooManySamples),
+}
+
+/// Returns whether there are at least `2^{num_groups}` available samples.
+fn enough_samples(available_samples: usize, num_groups: usize) -> bool {
+    match u32::try_from(num_groups)
+        .ok()
+        .and_then(|shift| 1_usize.checked_shl(shift))
+    {
+        Some(min_samples) => available_samples >= min_samples,
+        None => false, // No number of samples is that big
+    }
+}
+
+/// Number of groups `floor(log2(N)) - 1`, clamped, see [`Bootstrap::auto`].
+///
+/// [`Bootstrap::auto`]: struct.Bootstrap.html#method.auto
+fn auto_num_groups(available_samples: usize) -> usize {
+    let log2 = (usize::BITS - 1).saturating_sub(available_samples.leading_zeros()) as usize;
+    log2.saturating_sub(1)
+        .clamp(AUTO_MIN_NUM_GROUPS, AUTO_MAX_NUM_GROUPS)
+}
+
+/// Checks that the total number of repetitions, `Σ_{i < num_groups} 4^i`, fits in a `usize`.
+pub(super) fn check_samples_rep(num_groups: usize) -> Result<(), TooManyGroups> {
+    (0..num_groups)
+        .try_fold(0_usize, |total, i| {
+            4_usize
+                .checked_pow(i as u32)
+                .and_then(|rep| total.checked_add(rep))
+        })
+        .map(|_| ())
+        .ok_or(TooManyGroups)
+}
+
 impl<R> SamplingMethod for Bootstrap<R>
 where
     R: Rng,
 {
+    type Count = usize;
     type DegreeError = HighDegree;
-    type NumGroupsError = LowNumGroups;
-    type UnnormDistrError = TooFewSamples;
+    type NumGroupsError = NumGroupsError;
+    type UnnormDistrError = UnnormDistrError;
 
     fn degree(&self) -> usize {
         self.degree
@@ -108,81 +740,248 @@ where
     fn num_groups(&self) -> usize {
         self.num_groups
     }
+    /// Change the number of groups.
+    ///
+    /// Any custom size or repetition schedule is dropped, in favor of the default ones.
+    ///
+    /// # Errors
+    ///
+    /// If the number of groups is less or equal than the degree;
+    /// if there are too few samples: there must be at least `2^{num_groups}`;
+    /// or if the total number of repetitions does not fit in a `usize`.
     fn set_num_groups(
         &mut self,
         num_groups: usize,
     ) -> Result<&mut Bootstrap<R>, Self::NumGroupsError> {
-        if num_groups > self.degree {
-            self.num_groups = num_groups;
-            Ok(self)
-        } else {
-            Err(LowNumGroups)
+        if num_groups <= self.degree {
+            Err(LowNumGroups)?
+        }
+        if !enough_samples(self.available_samples(), num_groups) {
+            Err(TooFewSamples)?
         }
+        check_samples_rep(num_groups)?;
+        self.num_groups = num_groups;
+        self.size_schedule = None;
+        self.rep_schedule = None;
+        Ok(self)
     }
 
     /// Change the unnormalized distribution.
     ///
     /// # Errors
     ///
-    /// If there are too few samples: there must be at least `2^{num_groups}`.
+    /// If there are too few samples: there must be at least `2^{num_groups}`
+    /// or, with a custom size schedule, at least its greatest size;
+    /// or if the total number of samples does not fit in a `usize`.
     fn set_unnorm_distr(
         &mut self,
         unnorm_distr: &[usize],
     ) -> Result<&mut Self, Self::UnnormDistrError> {
-        let available_samples: usize = unnorm_distr.iter().sum();
-        if available_samples >= 1 << self.num_groups() {
+        let available_samples = checked_total(unnorm_distr).ok_or(TooManySamples)?;
+        let enough = match self.size_schedule.as_ref().and_then(|sizes| sizes.first()) {
+            Some(&max_size) => available_samples >= max_size,
+            None => enough_samples(available_samples, self.num_groups()),
+        };
+        if enough {
             self.unnorm_distr = unnorm_distr.to_vec();
+            self.bin_counts = None;
             Ok(self)
         } else {
-            Err(TooFewSamples)
+            Err(TooFewSamples)?
         }
     }
 
     fn size_subsamples(&self) -> Vec<usize> {
-        let available_samples: usize = self.unnorm_distr.iter().sum();
+        if let Some(sizes) = &self.size_schedule {
+            return sizes.clone();
+        }
+        let available_samples = self.available_samples();
         (0..self.num_groups())
-            .map(|i| available_samples >> i) // guaranteed to be at least 1
+            .map(|i| available_samples >> i) // positive unless built with `new_unchecked`, see `enough_samples`
             .collect()
     }
     fn samples_rep(&self) -> Vec<usize> {
+        if let Some(reps) = &self.rep_schedule {
+            return reps.clone();
+        }
         (0..self.num_groups())
-            .map(|i| 4_usize.pow(i as u32))
+            .map(|i| 4_usize.saturating_pow(i as u32)) // never saturates, see `check_samples_rep`
             .collect()
     }
 
+    fn pooled_unnorm_distr(&self) -> Vec<usize> {
+        self.unnorm_distr.clone()
+    }
+
+    fn dataset_size(&self) -> usize {
+        self.available_samples()
+    }
+
     fn naive_entropies(&mut self) -> Vec<(usize, f64)> {
         let mut naive_entropies = Vec::with_capacity(self.total_samples());
-        let sample_long = {
-            let mut vec = Vec::<usize>::new();
-            for j in 0..self.unnorm_distr.len() {
-                for _ in 0..self.unnorm_distr[j] {
-                    vec.push(j);
-                }
+        self.for_each_subsample(|group_size, unnorm_distr| {
+            let naive_entropy_value = NaiveEstimator::new_unchecked(unnorm_distr).entropy();
+            // Never fails because group_size is never null.
+            naive_entropies.push((group_size, naive_entropy_value));
+        });
+        naive_entropies
+    }
+
+    /// Draws each subsample only when the next estimation is requested.
+    fn naive_entropies_iter(&mut self) -> Box<dyn Iterator<Item = (usize, f64)> + '_> {
+        if self.bin_counts.is_none() {
+            self.bin_counts = Some(BinCounts::new(&self.unnorm_distr));
+        }
+        let groups = self.size_subsamples().into_iter().zip(self.samples_rep());
+        let replacement = self.replacement;
+        let bin_counts = self.bin_counts.as_ref().unwrap(); // Never fails, it was just set
+        let rng = &mut self.rng;
+
+        Box::new(
+            groups
+                .flat_map(|(group_size, repetitions)| (0..repetitions).map(move |_| group_size))
+                .map(move |group_size| {
+                    let seed = rng.gen::<u64>();
+                    let unnorm_distr =
+                        draw_seeded_subsample(bin_counts, group_size, replacement, seed);
+                    // Never fails because group_size is never null.
+                    let naive_entropy_value =
+                        NaiveEstimator::new_unchecked(&unnorm_distr).entropy();
+                    (group_size, naive_entropy_value)
+                }),
+        )
+    }
+}
+
+/// Summary of the configuration, without the unnormalized distribution.
+impl<R> fmt::Display for Bootstrap<R>
+where
+    R: Rng,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_summary("Bootstrap", self, f)
+    }
+}
+
+/// Counts of each bin of an unnormalized distribution, together with a Fenwick tree
+/// of them, so that bins can be drawn proportionally to their counts
+/// (and removed one sample at a time) in logarithmic time,
+/// without expanding the distribution to one element per sample.
+#[derive(Debug, Clone)]
+struct BinCounts {
+    counts: Vec<usize>,
+    tree: Vec<usize>,
+    total: usize,
+}
+
+impl BinCounts {
+    fn new(unnorm_distr: &[usize]) -> Self {
+        let mut tree = unnorm_distr.to_vec();
+        for i in 0..tree.len() {
+            let parent = i | (i + 1);
+            if parent < tree.len() {
+                tree[parent] += tree[i];
             }
-            vec
-        };
+        }
+        BinCounts {
+            counts: unnorm_distr.to_vec(),
+            tree,
+            total: unnorm_distr.iter().sum(),
+        }
+    }
 
-        let samples_rep = self.samples_rep();
-        for (group_index, group_size) in self.size_subsamples().iter().enumerate() {
-            for _ in 0..samples_rep[group_index] {
-                let rand_sample: Vec<usize> = sample_long
-                    .choose_multiple(&mut self.rng, *group_size)
-                    .cloned()
-                    .collect();
+    /// Returns the bin of the sample at `position`,
+    /// samples being ordered by bin.
+    fn find(&self, mut position: usize) -> usize {
+        let mut bin = 0;
+        let mut step = self.tree.len().checked_next_power_of_two().unwrap_or(0);
+        while step > 0 {
+            if bin + step <= self.tree.len() && self.tree[bin + step - 1] <= position {
+                bin += step;
+                position -= self.tree[bin - 1];
+            }
+            step >>= 1;
+        }
+        bin
+    }
 
-                let unnorm_distr = count_dup(&rand_sample);
-                let naive_entropy_value = NaiveEstimator::new_unchecked(&unnorm_distr).entropy();
-                // Never fails because group_size is never null.
-                naive_entropies.push((*group_size, naive_entropy_value));
+    /// Removes one sample from `bin`.
+    fn remove(&mut self, bin: usize) {
+        let mut i = bin;
+        while i < self.tree.len() {
+            self.tree[i] -= 1;
+            i |= i + 1;
+        }
+        self.total -= 1;
+    }
+
+    /// Draws a sample uniformly at random and returns its bin.
+    fn draw<G>(&self, rng: &mut G) -> usize
+    where
+        G: Rng,
+    {
+        self.find(rng.gen_range(0..self.total))
+    }
+}
+
+/// Draws `size` samples, with or without replacement,
+/// and returns how many were drawn from each bin (including empty ones).
+fn draw_subsample<G>(
+    bin_counts: &BinCounts,
+    size: usize,
+    replacement: bool,
+    rng: &mut G,
+) -> Vec<usize>
+where
+    G: Rng,
+{
+    let mut counts = vec![0; bin_counts.counts.len()];
+    if replacement {
+        for _ in 0..size {
+            counts[bin_counts.draw(rng)] += 1;
+        }
+    } else {
+        // Draw whichever is smaller: the samples to keep or the ones to leave out.
+        let num_draws = size.min(bin_counts.total - size);
+        let mut remaining = bin_counts.clone();
+        for _ in 0..num_draws {
+            let bin = remaining.draw(rng);
+            remaining.remove(bin);
+            counts[bin] += 1;
+        }
+        if num_draws < size {
+            for (count, available) in counts.iter_mut().zip(&bin_counts.counts) {
+                *count = available - *count;
             }
         }
-        naive_entropies
     }
+    counts
+}
+
+/// Draws a subsample with a generator seeded from `seed`
+/// and returns its unnormalized distribution (without empty bins).
+///
+/// Every subsample has its own seed, so that serial and parallel
+/// computations give the same subsamples.
+fn draw_seeded_subsample(
+    bin_counts: &BinCounts,
+    size: usize,
+    replacement: bool,
+    seed: u64,
+) -> Vec<usize> {
+    let mut rng = Pcg32::seed_from_u64(seed);
+    let mut unnorm_distr = draw_subsample(bin_counts, size, replacement, &mut rng);
+    unnorm_distr.retain(|&count| count > 0);
+    unnorm_distr
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use float_eq::assert_float_eq;
+    use test_case::test_case;
+
+    use crate::test::rng;
 
     #[test]
     fn new() {
@@ -192,6 +991,50 @@ mod tests {
         Bootstrap::new(&[1, 2, 3, 4, 5, 6], num_groups, degree, rng).unwrap();
     }
 
+    #[test_case(64; "shift_width")]
+    #[test_case(100; "huge")]
+    fn new_too_many_groups(num_groups: usize) {
+        let result = Bootstrap::new(&[1, 2, 3, 4, 5, 6], num_groups, 2, rng(1));
+        assert!(matches!(result, Err(ConstructionError::TooFewSamples(_))));
+    }
+
+    #[test]
+    fn new_too_many_samples() {
+        let result = Bootstrap::new(&[usize::MAX, 1], 3, 2, rng(1));
+        assert!(matches!(result, Err(ConstructionError::TooManySamples(_))));
+    }
+
+    #[test]
+    fn from_samples() {
+        let samples: Vec<usize> = (0..40).map(|i| (i * i) % 11).collect();
+        let mut bootstrap = Bootstrap::from_samples(&samples, 3, 2, rng(1)).unwrap();
+
+        let mut pooled = bootstrap.pooled_unnorm_distr();
+        let mut expected = crate::count_dup(&samples);
+        pooled.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(pooled, expected);
+
+        let unnorm_distr = count_dup_ordered(&samples);
+        let mut expected = Bootstrap::new(&unnorm_distr, 3, 2, rng(1)).unwrap();
+        assert_eq!(bootstrap.naive_entropies(), expected.naive_entropies());
+    }
+
+    #[test]
+    fn set_unnorm_distr() {
+        let mut bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        assert!(bootstrap.set_unnorm_distr(&[1, 2]).is_err());
+        assert!(bootstrap.set_unnorm_distr(&[4, 4]).is_ok());
+    }
+
+    #[test]
+    fn set_unnorm_distr_too_many_samples() {
+        let mut bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let result = bootstrap.set_unnorm_distr(&[usize::MAX, 1]);
+        assert!(matches!(result, Err(UnnormDistrError::TooManySamples(_))));
+        assert_eq!(bootstrap.dataset_size(), 21);
+    }
+
     #[test]
     fn size_subsamples() {
         let num_groups = 3;
@@ -212,6 +1055,29 @@ mod tests {
         assert_eq!(vec![1, 4, 16], bootstrap.samples_rep());
     }
 
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn samples_rep_many_groups() {
+        let mut bootstrap = Bootstrap::new(&[1; 512], 9, 2, rng(1)).unwrap();
+        assert_eq!(bootstrap.samples_rep()[8], 65536);
+        assert_eq!(bootstrap.total_samples(), 87381);
+        assert!(matches!(
+            bootstrap.set_num_groups(32),
+            Err(NumGroupsError::TooFewSamples(_))
+        ));
+        assert_eq!(bootstrap.num_groups(), 9);
+
+        // Enough samples for any number of groups whose repetitions fit
+        let mut bootstrap = Bootstrap::new(&[usize::MAX], 9, 2, rng(1)).unwrap();
+        assert!(bootstrap.set_num_groups(32).is_ok());
+        assert!(matches!(
+            bootstrap.set_num_groups(33),
+            Err(NumGroupsError::TooManyGroups(_))
+        ));
+        assert!(bootstrap.set_num_groups(100).is_err());
+        assert_eq!(bootstrap.num_groups(), 32);
+    }
+
     #[test]
     fn total_samples() {
         let num_groups = 3;
@@ -221,4 +1087,431 @@ mod tests {
 
         assert_eq!(21, bootstrap.total_samples());
     }
+
+    #[test_case(8, 2; "eight")]
+    #[test_case(16, 3; "sixteen")]
+    #[test_case(100, 5; "hundred")]
+    #[test_case(10_000, 6; "ten_thousand")]
+    #[test_case(1_000_000, 6; "million")]
+    fn auto(available_samples: usize, expected_num_groups: usize) {
+        let unnorm_distr = vec![1; available_samples];
+        let bootstrap = Bootstrap::auto(&unnorm_distr, rng(1)).unwrap();
+
+        assert_eq!(bootstrap.num_groups(), expected_num_groups);
+        assert_eq!(bootstrap.degree(), expected_num_groups - 1);
+        assert!(bootstrap.size_subsamples().iter().all(|&size| size > 0));
+    }
+
+    #[test_case(&[1, 1, 1]; "three")]
+    #[test_case(&[]; "empty")]
+    fn auto_too_few_samples(unnorm_distr: &[usize]) {
+        assert!(Bootstrap::auto(unnorm_distr, rng(1)).is_err());
+    }
+
+    #[test]
+    fn auto_too_many_samples() {
+        let result = Bootstrap::auto(&[usize::MAX, 1], rng(1));
+        assert!(matches!(result, Err(ConstructionError::TooManySamples(_))));
+    }
+
+    #[test]
+    fn dataset_size() {
+        let bootstrap = Bootstrap::new(&[10, 20, 30], 3, 2, rng(1)).unwrap();
+
+        assert_eq!(bootstrap.dataset_size(), 60);
+        assert_eq!(bootstrap.total_samples(), 21);
+    }
+
+    #[test]
+    fn unchecked_too_many_samples() {
+        let bootstrap = Bootstrap::new_unchecked(&[usize::MAX, 1], 3, 2, rng(1));
+
+        assert_eq!(bootstrap.dataset_size(), usize::MAX);
+        assert_eq!(
+            bootstrap.size_subsamples(),
+            vec![usize::MAX, usize::MAX >> 1, usize::MAX >> 2]
+        );
+    }
+
+    #[test]
+    fn builder() {
+        let expected = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        let mut bootstrap = Bootstrap::builder()
+            .unnorm_distr(&[1, 2, 3, 4, 5, 6])
+            .rng(rng(1))
+            .build()
+            .unwrap();
+
+        assert_eq!(bootstrap.num_groups(), expected.num_groups());
+        assert_eq!(bootstrap.degree(), expected.degree());
+        assert_eq!(bootstrap.naive_entropies().len(), expected.total_samples());
+    }
+
+    #[test_case(Bootstrap::builder().unnorm_distr(&[1, 2]); "too_few_samples")]
+    #[test_case(Bootstrap::builder().unnorm_distr(&[1; 100]).degree(3); "low_num_groups")]
+    #[test_case(Bootstrap::builder(); "empty")]
+    fn builder_invalid(builder: BootstrapBuilder<ThreadRng>) {
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn fork() {
+        let rng = rand_pcg::Pcg32::seed_from_u64(0);
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng).unwrap();
+        let mut first = bootstrap.fork(1);
+        let mut second = bootstrap.fork(2);
+
+        assert_eq!(first.size_subsamples(), second.size_subsamples());
+        assert_ne!(first.naive_entropies(), second.naive_entropies());
+    }
+
+    #[test]
+    fn display() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        assert_eq!(
+            bootstrap.to_string(),
+            "Bootstrap(num_groups: 3, degree: 2, dataset_size: 21, total_samples: 21, \
+             size_subsamples: [21, 10, 5], samples_rep: [1, 4, 16])"
+        );
+    }
+
+    #[test]
+    fn with_size_schedule() {
+        let mut bootstrap = Bootstrap::new(&[10, 20, 30, 40], 3, 2, rng(1))
+            .unwrap()
+            .with_size_schedule(vec![20, 40, 60, 80, 100])
+            .unwrap();
+
+        assert_eq!(bootstrap.num_groups(), 5);
+        assert_eq!(bootstrap.size_subsamples(), vec![100, 80, 60, 40, 20]);
+        let naive_entropies = bootstrap.naive_entropies();
+        assert_eq!(naive_entropies.len(), bootstrap.total_samples());
+        assert!(naive_entropies
+            .iter()
+            .all(|(size, _)| [20, 40, 60, 80, 100].contains(size)));
+
+        assert!(bootstrap.set_unnorm_distr(&[10, 20]).is_err());
+        bootstrap.set_num_groups(3).unwrap();
+        assert_eq!(bootstrap.size_subsamples(), vec![100, 50, 25]);
+    }
+
+    /// Only the greatest size of the schedule matters, not `2^{num_groups}`
+    #[test]
+    fn set_unnorm_distr_with_size_schedule() {
+        let mut bootstrap = Bootstrap::new(&[10, 20, 30, 40], 3, 2, rng(1))
+            .unwrap()
+            .with_size_schedule(vec![1, 2, 3, 4, 5, 6])
+            .unwrap();
+
+        assert!(bootstrap.set_unnorm_distr(&[3, 3]).is_ok());
+        assert_eq!(bootstrap.dataset_size(), 6);
+        assert!(bootstrap.set_unnorm_distr(&[2, 3]).is_err());
+    }
+
+    #[test]
+    fn can_fit() {
+        let bootstrap = Bootstrap::new(&[10, 20, 30, 40], 3, 2, rng(1)).unwrap();
+        assert!(bootstrap.can_fit());
+
+        let bootstrap = bootstrap.with_size_schedule(vec![50, 25, 10]).unwrap();
+        assert!(bootstrap.can_fit());
+    }
+
+    #[test]
+    fn with_size_schedule_duplicate() {
+        let bootstrap = Bootstrap::new(&[10, 20, 30, 40], 3, 2, rng(1)).unwrap();
+        let result = bootstrap.with_size_schedule(vec![50, 25, 50, 10]);
+        assert!(matches!(
+            result,
+            Err(ScheduleError::DuplicateSize { size: 50 })
+        ));
+    }
+
+    #[test_case(vec![0, 5, 10]; "zero")]
+    #[test_case(vec![5, 10, 22]; "too_big")]
+    #[test_case(vec![5, 10]; "too_few_sizes")]
+    fn with_size_schedule_invalid(sizes: Vec<usize>) {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        assert!(bootstrap.with_size_schedule(sizes).is_err());
+    }
+
+    #[test]
+    fn with_rep_schedule() {
+        let bootstrap = Bootstrap::new(&[10, 20, 30, 40], 3, 2, rng(1)).unwrap();
+        assert_eq!(bootstrap.total_samples(), 21);
+
+        let mut bootstrap = bootstrap.with_rep_schedule(vec![1, 2, 50]).unwrap();
+        assert_eq!(bootstrap.samples_rep(), vec![1, 2, 50]);
+        assert_eq!(bootstrap.total_samples(), 53);
+        let naive_entropies = bootstrap.naive_entropies();
+        assert_eq!(naive_entropies.len(), 53);
+        assert_eq!(
+            naive_entropies
+                .iter()
+                .filter(|(size, _)| *size == 25)
+                .count(),
+            50
+        );
+
+        let bootstrap = bootstrap
+            .with_size_schedule(vec![30, 60, 90, 100])
+            .unwrap()
+            .with_rep_schedule(vec![1, 1, 1, 1])
+            .unwrap();
+        assert_eq!(bootstrap.total_samples(), 4);
+    }
+
+    #[test_case(vec![1, 4]; "too_short")]
+    #[test_case(vec![1, 4, 16, 64]; "too_long")]
+    #[test_case(vec![1, 0, 16]; "zero")]
+    #[test_case(vec![1, usize::MAX, 16]; "overflow")]
+    fn with_rep_schedule_invalid(reps: Vec<usize>) {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        assert!(bootstrap.with_rep_schedule(reps).is_err());
+    }
+
+    /// Same values whether the cumulative counts are cached or built again
+    #[test]
+    fn cached_bin_counts() {
+        let unnorm_distr = [1, 2, 3, 4, 5, 6];
+        let mut cached =
+            Bootstrap::new(&unnorm_distr, 3, 2, rand_pcg::Pcg32::seed_from_u64(0)).unwrap();
+        cached.naive_entropies();
+        assert!(cached.bin_counts.is_some());
+        cached.reseed(1);
+
+        let mut uncached =
+            Bootstrap::new(&unnorm_distr, 3, 2, rand_pcg::Pcg32::seed_from_u64(1)).unwrap();
+        let expected = uncached.naive_entropies();
+
+        let naive_entropies = cached.naive_entropies();
+        assert_eq!(naive_entropies.len(), expected.len());
+        for ((size, value), (expected_size, expected_value)) in
+            naive_entropies.iter().zip(&expected)
+        {
+            assert_eq!(size, expected_size);
+            assert_float_eq!(*value, *expected_value, abs <= 1e-12);
+        }
+    }
+
+    #[test]
+    fn set_unnorm_distr_clears_cache() {
+        let mut bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1)).unwrap();
+        bootstrap.naive_entropies();
+        bootstrap.set_unnorm_distr(&[8]).unwrap();
+        assert!(bootstrap.bin_counts.is_none());
+
+        let naive_entropies = bootstrap.naive_entropies();
+        assert!(naive_entropies.iter().all(|&(_, value)| value == 0.));
+    }
+
+    #[test]
+    fn bin_counts_find() {
+        let bin_counts = BinCounts::new(&[2, 0, 3, 1, 0, 4]);
+        let bins: Vec<usize> = (0..10).map(|position| bin_counts.find(position)).collect();
+        assert_eq!(bins, vec![0, 0, 2, 2, 2, 3, 5, 5, 5, 5]);
+    }
+
+    /// Subsamples drawn from the expanded samples, as before `BinCounts`.
+    fn draw_subsample_expanded<G>(
+        unnorm_distr: &[usize],
+        size: usize,
+        replacement: bool,
+        rng: &mut G,
+    ) -> Vec<usize>
+    where
+        G: Rng,
+    {
+        use rand::seq::SliceRandom;
+
+        let mut sample_long = Vec::<usize>::new();
+        for (bin, &count) in unnorm_distr.iter().enumerate() {
+            for _ in 0..count {
+                sample_long.push(bin);
+            }
+        }
+        let rand_sample: Vec<usize> = if replacement {
+            (0..size)
+                .map(|_| sample_long[rng.gen_range(0..sample_long.len())])
+                .collect()
+        } else {
+            sample_long.choose_multiple(rng, size).cloned().collect()
+        };
+        let mut counts = vec![0; unnorm_distr.len()];
+        rand_sample.iter().for_each(|&bin| counts[bin] += 1);
+        counts
+    }
+
+    #[test_case(3, false; "small_without_replacement")]
+    #[test_case(6, false; "large_without_replacement")]
+    #[test_case(3, true; "with_replacement")]
+    fn draw_subsample_distribution(size: usize, replacement: bool) {
+        let unnorm_distr = [4, 0, 2, 1];
+        let num_draws = 20_000;
+        let frequencies = |draw: &mut dyn FnMut(&mut rand_pcg::Pcg32) -> Vec<usize>| {
+            let mut rng = rand_pcg::Pcg32::seed_from_u64(0);
+            let mut frequencies = std::collections::HashMap::new();
+            for _ in 0..num_draws {
+                *frequencies.entry(draw(&mut rng)).or_insert(0.) += 1. / num_draws as f64;
+            }
+            frequencies
+        };
+
+        let bin_counts = BinCounts::new(&unnorm_distr);
+        let new = frequencies(&mut |rng| draw_subsample(&bin_counts, size, replacement, rng));
+        let old =
+            frequencies(&mut |rng| draw_subsample_expanded(&unnorm_distr, size, replacement, rng));
+
+        for counts in old.keys().chain(new.keys()) {
+            assert_eq!(counts.iter().sum::<usize>(), size);
+            let old_frequency = old.get(counts).copied().unwrap_or(0.);
+            let new_frequency = new.get(counts).copied().unwrap_or(0.);
+            assert_float_eq!(old_frequency, new_frequency, abs <= 0.02);
+        }
+    }
+
+    #[test]
+    fn huge_bin() {
+        let mut bootstrap = Bootstrap::new(&[1_000_000, 2, 3], 3, 2, rng(1)).unwrap();
+        assert!(bootstrap.bin_counts.is_none());
+        let naive_entropies = bootstrap.naive_entropies();
+        assert_eq!(naive_entropies.len(), bootstrap.total_samples());
+    }
+
+    #[test]
+    fn naive_entropies_iter() {
+        let rng = rand_pcg::Pcg32::seed_from_u64(3);
+        let mut bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng).unwrap();
+        let mut other = bootstrap.clone();
+
+        let expected = bootstrap.naive_entropies();
+        let naive_entropies: Vec<(usize, f64)> = other.naive_entropies_iter().collect();
+        assert_eq!(naive_entropies, expected);
+        assert_eq!(bootstrap.naive_entropies(), other.naive_entropies());
+    }
+
+    #[test]
+    fn split() {
+        let split = || {
+            let rng = rand_pcg::Pcg32::seed_from_u64(0);
+            let mut parent = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng).unwrap();
+            let mut child = parent.split();
+            (parent.naive_entropies(), child.naive_entropies())
+        };
+        let (parent, child) = split();
+
+        assert_ne!(parent, child);
+        assert_eq!(split(), (parent, child));
+    }
+
+    #[test_case(&[1; 1000], 1.; "huge_support")]
+    #[test_case(&[1000], 0.; "one_element")]
+    fn singleton_fraction(unnorm_distr: &[usize], expected: f64) {
+        let mut bootstrap = Bootstrap::new(unnorm_distr, 5, 2, rng(1)).unwrap();
+        assert_float_eq!(bootstrap.singleton_fraction(), expected, abs <= 1e-12);
+    }
+
+    /// Same values as the serial version, with one thread or several threads
+    #[cfg(feature = "rayon")]
+    #[test_case(1; "one_thread")]
+    #[test_case(4; "four_threads")]
+    fn naive_entropies_par(num_threads: usize) {
+        let mut bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 4, 2, rng(1)).unwrap();
+        let serial = bootstrap.clone().naive_entropies();
+        let parallel = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap()
+            .install(|| bootstrap.naive_entropies_par());
+
+        assert_eq!(serial.len(), 85);
+        assert_eq!(parallel, serial);
+    }
+
+    /// With replacement, subsamples of distinct samples contain duplicates
+    #[test_case(false, 1.; "without_replacement")]
+    #[test_case(true, 0.; "with_replacement")]
+    fn singleton_fraction_replacement(replacement: bool, expected: f64) {
+        let mut bootstrap = Bootstrap::new(&[1; 1000], 2, 1, rng(1))
+            .unwrap()
+            .with_replacement(replacement);
+
+        let mut num_duplicated = 0;
+        bootstrap.for_each_subsample(|group_size, unnorm_distr| {
+            assert_eq!(unnorm_distr.iter().sum::<usize>(), group_size);
+            if unnorm_distr.len() < group_size {
+                num_duplicated += 1;
+            }
+        });
+        assert_float_eq!(bootstrap.singleton_fraction(), expected, abs <= 1e-12);
+        assert_eq!(num_duplicated > 0, replacement);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rand::thread_rng()).unwrap();
+        let serialized = serde_json::to_string(&bootstrap).unwrap();
+        let deserialized: Bootstrap<rand::rngs::ThreadRng> =
+            serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(bootstrap.size_subsamples(), deserialized.size_subsamples());
+        assert_eq!(bootstrap.samples_rep(), deserialized.samples_rep());
+        assert_eq!(bootstrap.degree(), deserialized.degree());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test_case(r#"{"num_groups":3,"degree":3,"unnorm_distr":[1,2,3,4,5,6]}"#; "low_num_groups")]
+    #[test_case(r#"{"num_groups":3,"degree":2,"unnorm_distr":[1,2]}"#; "too_few_samples")]
+    #[test_case(r#"{"num_groups":2,"degree":1,"unnorm_distr":[1,2],"size_schedule":[4,2]}"#; "size_out_of_range")]
+    #[test_case(r#"{"num_groups":3,"degree":2,"unnorm_distr":[1,2,3,4,5,6],"rep_schedule":[1,0,1]}"#; "zero_repetitions")]
+    fn serde_invalid(json: &str) {
+        let deserialized: Result<Bootstrap<rand::rngs::ThreadRng>, _> = serde_json::from_str(json);
+        assert!(deserialized.is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_schedules() {
+        let bootstrap = Bootstrap::new(&[1, 2, 3, 4, 5, 6], 3, 2, rng(1))
+            .unwrap()
+            .with_size_schedule(vec![20, 15, 10, 5])
+            .unwrap()
+            .with_rep_schedule(vec![1, 2, 3, 4])
+            .unwrap()
+            .with_replacement(true);
+        let serialized = serde_json::to_string(&bootstrap).unwrap();
+        let deserialized: Bootstrap<rand::rngs::ThreadRng> =
+            serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(bootstrap.size_subsamples(), deserialized.size_subsamples());
+        assert_eq!(bootstrap.samples_rep(), deserialized.samples_rep());
+        assert_eq!(bootstrap.replacement, deserialized.replacement);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn from_ndarray() {
+        let unnorm_distr = [1, 2, 3, 4, 5, 6];
+        let array = ndarray::Array1::from(unnorm_distr.to_vec());
+        let mut from_slice = Bootstrap::new(&unnorm_distr, 3, 2, rng(1)).unwrap();
+        let mut from_array = Bootstrap::from_ndarray(array.view(), 3, 2, rng(1)).unwrap();
+        for ((size, value), (expected_size, expected_value)) in from_array
+            .naive_entropies()
+            .iter()
+            .zip(&from_slice.naive_entropies())
+        {
+            assert_eq!(size, expected_size);
+            assert_float_eq!(value, expected_value, abs <= 1e-12);
+        }
+
+        // Not contiguous
+        let array = ndarray::Array1::from(vec![1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6, 0]);
+        let strided = array.slice(ndarray::s![..;2]);
+        let from_strided = Bootstrap::from_ndarray(strided, 3, 2, rng(1)).unwrap();
+        assert_eq!(
+            from_strided.pooled_unnorm_distr(),
+            from_slice.pooled_unnorm_distr()
+        );
+    }
 }
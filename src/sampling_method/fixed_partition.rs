@@ -1,16 +1,57 @@
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+use core::{fmt, hash::Hash};
+use nalgebra::DMatrix;
 use rand::{seq::SliceRandom, Rng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{traits::SamplingMethod, utils::count_dup, NaiveEstimator};
 
-#[derive(Debug, Clone)]
-pub struct FixedPartition {
-    samples: Vec<usize>,
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(
+        try_from = "FixedPartitionData<T>",
+        bound(deserialize = "T: Deserialize<'de> + Hash + Eq + Clone")
+    )
+)]
+pub struct FixedPartition<T = usize> {
+    samples: Vec<T>,
     size_subsamples: Vec<usize>,
     samples_rep: Vec<usize>,
     degree: usize,
 }
 
+/// Serialized fields of a [`FixedPartition`], validated on deserialization.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct FixedPartitionData<T> {
+    samples: Vec<T>,
+    size_subsamples: Vec<usize>,
+    samples_rep: Vec<usize>,
+    degree: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<FixedPartitionData<T>> for FixedPartition<T>
+where
+    T: Hash + Eq + Clone,
+{
+    type Error = ConstructionError;
+
+    fn try_from(data: FixedPartitionData<T>) -> Result<Self, Self::Error> {
+        FixedPartition::new(
+            &data.samples,
+            &data.size_subsamples,
+            &data.samples_rep,
+            data.degree,
+        )
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ConstructionError {
     #[error(
@@ -35,7 +76,20 @@ pub enum ConstructionError {
 ///
 /// Although the name is `FixedPartition`, strictly speaking it is a sub-partition:
 /// there can be more samples than necessary. The extra samples are not used.
-impl FixedPartition {
+///
+/// Samples can be of any type that can be counted, not only `usize`.
+///
+/// The partition follows the order of the samples, which is intentional:
+/// subsamples are taken from the end of `samples` backwards,
+/// so two orderings of the same samples give different naive entropies.
+/// Use [`shuffle`] to partition a random ordering instead,
+/// making estimations independent of the input order in expectation.
+///
+/// [`shuffle`]: #method.shuffle
+impl<T> FixedPartition<T>
+where
+    T: Hash + Eq + Clone,
+{
     /// Construct a new `FixedPartition`.
     ///
     /// # Input
@@ -61,7 +115,7 @@ impl FixedPartition {
     /// FixedPartition::new(&samples, &size_subsamples, &samples_rep, degree).unwrap();
     /// ```
     pub fn new(
-        samples: &[usize],
+        samples: &[T],
         size_subsamples: &[usize],
         samples_rep: &[usize],
         degree: usize,
@@ -100,7 +154,7 @@ impl FixedPartition {
 
     /// Construct a new `Bootstrap`.
     pub fn new_unchecked(
-        samples: &[usize],
+        samples: &[T],
         size_subsamples: &[usize],
         samples_rep: &[usize],
         degree: usize,
@@ -116,11 +170,70 @@ impl FixedPartition {
     /// Shuffle the sample in place.
     ///
     /// Useful to generate a different entropy estimation
-    /// which is just as valid as any other.
+    /// which is just as valid as any other,
+    /// or to remove the dependence on the order in which samples were given.
     pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) -> &mut Self {
         self.samples.shuffle(rng);
         self
     }
+
+    /// Returns the naive entropy estimations with their groups, one row per subsample,
+    /// in the same order as [`naive_entropies`].
+    ///
+    /// Columns are:
+    /// 0. the size of the subsample,
+    /// 1. the number of repetitions of its group, i.e. of subsamples with that size,
+    /// 2. the naive entropy of the subsample.
+    ///
+    /// Useful to audit what the partition extracted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use approx_entropy::FixedPartition;
+    /// let samples = [0, 1, 0, 1, 0, 0, 1, 1];
+    /// let fixed = FixedPartition::new(&samples, &[4, 2], &[1, 2], 1).unwrap();
+    /// let matrix = fixed.entropy_matrix();
+    /// assert_eq!(matrix.shape(), (3, 3));
+    /// assert_eq!(matrix[(1, 0)], 2.); // size of the second subsample
+    /// assert_eq!(matrix[(1, 1)], 2.); // there are two subsamples of size 2
+    /// ```
+    ///
+    /// [`naive_entropies`]: trait.SamplingMethod.html#tymethod.naive_entropies
+    pub fn entropy_matrix(&self) -> DMatrix<f64> {
+        let repetitions = self
+            .samples_rep
+            .iter()
+            .flat_map(|&rep| (0..rep).map(move |_| rep));
+        let rows: Vec<[f64; 3]> = self
+            .subsample_entropies()
+            .into_iter()
+            .zip(repetitions)
+            .map(|((size, value), rep)| [size as f64, rep as f64, value])
+            .collect();
+        DMatrix::from_fn(rows.len(), 3, |i, j| rows[i][j])
+    }
+
+    /// Naive entropies of consecutive subsamples, see `naive_entropies`.
+    fn subsample_entropies(&self) -> Vec<(usize, f64)> {
+        let mut naive_entropies = Vec::with_capacity(self.total_samples());
+        let mut sample_long = self.samples.clone();
+
+        for (group_index, group_size) in self.size_subsamples.iter().enumerate() {
+            let repetitions = self.samples_rep[group_index];
+            for _ in 0..repetitions {
+                let sub_sample: Vec<T> = (0..*group_size)
+                    .map(|_| sample_long.pop().unwrap()) // Never fails by construction conditions of FixedPartition
+                    .collect();
+                let unnorm_distr = count_dup(&sub_sample);
+                let naive_entropy_value = NaiveEstimator::new_unchecked(&unnorm_distr).entropy();
+                // Never fails because there is no null group_size
+
+                naive_entropies.push((*group_size, naive_entropy_value));
+            }
+        }
+        naive_entropies
+    }
 }
 
 #[derive(Error, Debug)]
@@ -131,7 +244,11 @@ pub struct TooHighDegree;
 #[error("Invalid unnormalized distribution: the total number of samples is too low.")]
 pub struct Immutable;
 
-impl SamplingMethod for FixedPartition {
+impl<T> SamplingMethod for FixedPartition<T>
+where
+    T: Hash + Eq + Clone,
+{
+    type Count = usize;
     type DegreeError = TooHighDegree;
     type NumGroupsError = Immutable;
     type UnnormDistrError = Immutable;
@@ -173,24 +290,32 @@ impl SamplingMethod for FixedPartition {
         self.samples_rep.clone()
     }
 
-    fn naive_entropies(&mut self) -> Vec<(usize, f64)> {
-        let mut naive_entropies = Vec::with_capacity(self.total_samples());
-        let mut sample_long = self.samples.clone();
+    fn pooled_unnorm_distr(&self) -> Vec<usize> {
+        count_dup(&self.samples)
+    }
 
-        for (group_index, group_size) in self.size_subsamples().iter().enumerate() {
-            let repetitions = self.samples_rep()[group_index];
-            for _ in 0..repetitions {
-                let sub_sample: Vec<usize> = (0..*group_size)
-                    .map(|_| sample_long.pop().unwrap()) // Never fails by construction conditions of FixedPartition
-                    .collect();
-                let unnorm_distr = count_dup(&sub_sample);
-                let naive_entropy_value = NaiveEstimator::new_unchecked(&unnorm_distr).entropy();
-                // Never fails because there is no null group_size
+    fn dataset_size(&self) -> usize {
+        self.samples.len()
+    }
 
-                naive_entropies.push((*group_size, naive_entropy_value));
-            }
-        }
-        naive_entropies
+    /// Naive entropies of consecutive subsamples,
+    /// taken from the end of the samples backwards.
+    ///
+    /// The result depends on the order of the samples, see [`shuffle`].
+    ///
+    /// [`shuffle`]: struct.FixedPartition.html#method.shuffle
+    fn naive_entropies(&mut self) -> Vec<(usize, f64)> {
+        self.subsample_entropies()
+    }
+}
+
+/// Summary of the configuration, without the samples.
+impl<T> fmt::Display for FixedPartition<T>
+where
+    T: Hash + Eq + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::fmt_summary("FixedPartition", self, f)
     }
 }
 
@@ -242,6 +367,14 @@ mod tests {
         assert_eq!(samples_rep.len(), fixed.total_samples());
     }
 
+    #[test]
+    fn dataset_size() {
+        let samples = [0, 0, 0, 1, 1, 2];
+        let fixed = FixedPartition::new(&samples, &[3, 2, 1], &[1, 1, 1], 2).unwrap();
+
+        assert_eq!(samples.len(), fixed.dataset_size());
+    }
+
     // All naive entropy estimations are zero in this case.
     #[test_case(
         &[0, 0, 0, 1, 1, 1, 1, 2, 2, 2],  //
@@ -277,4 +410,144 @@ mod tests {
             assert_float_eq!(naive_entropy_value, expected_value, abs <= 1e-6);
         }
     }
+
+    #[test]
+    fn naive_entropies_iter() {
+        let samples = [0, 0, 0, 1, 1, 1, 1, 2, 2, 2];
+        let mut fixed = FixedPartition::new(&samples, &[3, 2, 1], &[1, 2, 3], 2).unwrap();
+        let naive_entropies: Vec<(usize, f64)> = fixed.naive_entropies_iter().collect();
+
+        assert_eq!(naive_entropies, fixed.naive_entropies());
+    }
+
+    #[test]
+    fn entropy_matrix() {
+        let samples = [0, 0, 0, 1, 1, 1, 1, 2, 2, 2];
+        let mut fixed = FixedPartition::new(&samples, &[3, 2, 1], &[1, 2, 3], 2).unwrap();
+        let matrix = fixed.entropy_matrix();
+        let naive_entropies = fixed.naive_entropies();
+
+        assert_eq!(matrix.shape(), (6, 3));
+        assert_eq!(matrix.column(1).as_slice(), &[1., 2., 2., 3., 3., 3.]);
+        for (row, (size, value)) in naive_entropies.into_iter().enumerate() {
+            assert_eq!(matrix[(row, 0)], size as f64);
+            assert_float_eq!(matrix[(row, 2)], value, abs <= 1e-12);
+        }
+    }
+
+    #[test]
+    fn chars() {
+        let samples = ['a', 'b', 'a', 'b', 'c', 'c', 'a', 'b', 'a', 'b'];
+        let mut fixed = FixedPartition::new(&samples, &[4, 2], &[1, 3], 1).unwrap();
+        let indices = [0, 1, 0, 1, 2, 2, 0, 1, 0, 1];
+        let mut indexed = FixedPartition::new(&indices, &[4, 2], &[1, 3], 1).unwrap();
+
+        let mut pooled = fixed.pooled_unnorm_distr();
+        pooled.sort_unstable();
+        assert_eq!(pooled, vec![2, 4, 4]);
+        for ((size, value), (expected_size, expected_value)) in fixed
+            .naive_entropies()
+            .iter()
+            .zip(indexed.naive_entropies().iter())
+        {
+            assert_eq!(size, expected_size);
+            assert_float_eq!(value, expected_value, abs <= 1e-12);
+        }
+    }
+
+    #[test]
+    fn clone_eq() {
+        let samples = [0, 0, 0, 1, 1, 2];
+        let fixed = FixedPartition::new(&samples, &[3, 2, 1], &[1, 1, 1], 2).unwrap();
+        let mut cloned = fixed.clone();
+        assert_eq!(fixed, cloned);
+
+        cloned.set_degree(1).unwrap();
+        assert_ne!(fixed, cloned);
+    }
+
+    #[test_case(&[3, 2, 1], 2, true; "feasible")]
+    #[test_case(&[3, 3, 1], 1, true; "feasible_with_duplicates")]
+    #[test_case(&[3, 3, 1], 2, false; "infeasible")]
+    fn can_fit(size_subsamples: &[usize], degree: usize, expected: bool) {
+        let samples = [0, 0, 0, 1, 1, 2, 2];
+        let fixed = FixedPartition::new(&samples, size_subsamples, &[1, 1, 1], degree).unwrap();
+        assert_eq!(fixed.can_fit(), expected);
+    }
+
+    #[test]
+    fn display() {
+        let samples = [0, 0, 0, 1, 1, 2];
+        let fixed = FixedPartition::new(&samples, &[3, 2, 1], &[1, 1, 1], 2).unwrap();
+        let summary = fixed.to_string();
+
+        assert!(summary.starts_with("FixedPartition("));
+        assert!(summary.contains("num_groups: 3"));
+        assert!(summary.contains("degree: 2"));
+        assert!(summary.contains("dataset_size: 6"));
+        assert!(summary.contains("size_subsamples: [3, 2, 1]"));
+        assert!(!summary.contains("[0, 0, 0, 1, 1, 2]"));
+    }
+
+    #[test]
+    fn order_dependence() {
+        let sorted = [0, 0, 0, 0, 1, 1, 1, 1];
+        let alternating = [0, 1, 0, 1, 0, 1, 0, 1];
+        let naive_entropies = |samples: &[usize]| {
+            FixedPartition::new(samples, &[4, 2], &[1, 2], 1)
+                .unwrap()
+                .naive_entropies()
+        };
+
+        assert_ne!(naive_entropies(&sorted), naive_entropies(&alternating));
+    }
+
+    /// After shuffling, the order of the samples does not matter in expectation
+    #[test]
+    fn shuffle_order_independent() {
+        let sorted = [0, 0, 0, 0, 1, 1, 1, 1];
+        let alternating = [0, 1, 0, 1, 0, 1, 0, 1];
+        let reps = 4000;
+        let mean_naive_entropies = |samples: &[usize]| {
+            let mut rng = crate::test::rng(1);
+            let mut fixed = FixedPartition::new(samples, &[4, 2], &[1, 2], 1).unwrap();
+            let mut means = vec![0.; fixed.total_samples()];
+            for _ in 0..reps {
+                let naive_entropies = fixed.shuffle(&mut rng).naive_entropies();
+                for (mean, (_, value)) in means.iter_mut().zip(naive_entropies) {
+                    *mean += value / reps as f64;
+                }
+            }
+            means
+        };
+
+        for (sorted_mean, alternating_mean) in mean_naive_entropies(&sorted)
+            .iter()
+            .zip(mean_naive_entropies(&alternating))
+        {
+            assert_float_eq!(*sorted_mean, alternating_mean, abs <= 0.05);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let samples = [0, 0, 0, 1, 1, 2];
+        let fixed = FixedPartition::new(&samples, &[3, 2, 1], &[1, 1, 1], 2).unwrap();
+        let serialized = serde_json::to_string(&fixed).unwrap();
+        let deserialized: FixedPartition = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(fixed.size_subsamples(), deserialized.size_subsamples());
+        assert_eq!(fixed.samples_rep(), deserialized.samples_rep());
+        assert_eq!(fixed.degree(), deserialized.degree());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test_case(r#"{"samples":[0,0,1],"size_subsamples":[2,1],"samples_rep":[1,1],"degree":2}"#; "low_num_groups")]
+    #[test_case(r#"{"samples":[0,0,1],"size_subsamples":[2,1],"samples_rep":[1,2],"degree":1}"#; "too_few_samples")]
+    #[test_case(r#"{"samples":[0,0,1],"size_subsamples":[2,1],"samples_rep":[1],"degree":1}"#; "too_many_subsample_sizes")]
+    fn serde_invalid(json: &str) {
+        let deserialized: Result<FixedPartition, _> = serde_json::from_str(json);
+        assert!(deserialized.is_err());
+    }
 }
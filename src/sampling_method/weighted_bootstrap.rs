@@ -0,0 +1,375 @@
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::bootstrap::{
+    check_samples_rep, HighDegree, LowNumGroups, NumGroupsError, TooFewSamples, TooManyGroups,
+};
+use crate::{
+    traits::{Reseed, SamplingMethod},
+    NaiveEstimator,
+};
+
+/// Bootstrap sampling from an unnormalized distribution with fractional counts.
+///
+/// It behaves like [`Bootstrap`], but each entry of the unnormalized distribution
+/// is a weight instead of a number of occurrences.
+/// Subsamples are drawn without replacement: each draw picks an element
+/// with probability proportional to its remaining weight, which is then
+/// decreased by one (or set to zero if it was smaller than one).
+///
+/// When all weights are whole numbers, this is the same distribution of subsamples
+/// as [`Bootstrap`].
+///
+/// [`Bootstrap`]: struct.Bootstrap.html
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(
+        try_from = "WeightedBootstrapData",
+        bound(deserialize = "R: Rng + Default")
+    )
+)]
+pub struct WeightedBootstrap<R> {
+    num_groups: usize,
+    degree: usize,
+    unnorm_distr: Vec<f64>,
+    /// Not serialized, a default one is used on deserialization.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    rng: R,
+}
+
+/// Serialized fields of a [`WeightedBootstrap`], validated on deserialization.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct WeightedBootstrapData {
+    num_groups: usize,
+    degree: usize,
+    unnorm_distr: Vec<f64>,
+}
+
+#[cfg(feature = "serde")]
+impl<R> TryFrom<WeightedBootstrapData> for WeightedBootstrap<R>
+where
+    R: Rng + Default,
+{
+    type Error = ConstructionError;
+
+    fn try_from(data: WeightedBootstrapData) -> Result<Self, Self::Error> {
+        WeightedBootstrap::new(
+            &data.unnorm_distr,
+            data.num_groups,
+            data.degree,
+            R::default(),
+        )
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ConstructionError {
+    #[error(
+        "Failed construction. There are too few samples (or the number of groups is too big)."
+    )]
+    TooFewSamples(#[from] TooFewSamples),
+    #[error("Failed construction. There are too few number of groups (or the degree is too big).")]
+    LowNumGroups(#[from] LowNumGroups),
+    #[error("Failed construction. There are too many groups.")]
+    TooManyGroups(#[from] TooManyGroups),
+    #[error("Failed construction. There is a count which is negative or not finite.")]
+    InvalidCount(#[from] InvalidCount),
+}
+
+#[derive(Error, Debug)]
+#[error("Invalid unnormalized distribution: counts must be finite and non-negative.")]
+pub struct InvalidCount;
+
+#[derive(Error, Debug)]
+pub enum UnnormDistrError {
+    #[error(transparent)]
+    TooFewSamples(#[from] TooFewSamples),
+    #[error(transparent)]
+    InvalidCount(#[from] InvalidCount),
+}
+
+impl<R> WeightedBootstrap<R>
+where
+    R: Rng,
+{
+    /// Construct a new `WeightedBootstrap`.
+    ///
+    /// # Errors
+    ///
+    /// If the number of groups is less or equal than the degree;
+    /// if the total number of repetitions does not fit in a `usize`;
+    /// if there is a negative or non finite count;
+    /// or if the total weight is too low (for the desired number of groups).
+    pub fn new(
+        unnorm_distr: &[f64],
+        num_groups: usize,
+        degree: usize,
+        rng: R,
+    ) -> Result<Self, ConstructionError> {
+        if num_groups <= degree {
+            Err(LowNumGroups)?
+        }
+        check_samples_rep(num_groups)?;
+        check_counts(unnorm_distr)?;
+        if !enough_weight(unnorm_distr, num_groups) {
+            Err(TooFewSamples)?
+        }
+        Ok(WeightedBootstrap::new_unchecked(
+            unnorm_distr,
+            num_groups,
+            degree,
+            rng,
+        ))
+    }
+
+    /// Construct a new `WeightedBootstrap`.
+    pub fn new_unchecked(unnorm_distr: &[f64], num_groups: usize, degree: usize, rng: R) -> Self {
+        WeightedBootstrap {
+            num_groups,
+            degree,
+            unnorm_distr: unnorm_distr.to_vec(),
+            rng,
+        }
+    }
+
+    /// Draws a subsample of size `size` without replacement
+    /// and returns its unnormalized distribution.
+    fn draw_unnorm_distr(&mut self, size: usize) -> Vec<usize> {
+        let mut remaining = self.unnorm_distr.clone();
+        let mut remaining_weight = total_weight(&remaining);
+        let mut counts = vec![0; remaining.len()];
+
+        for _ in 0..size {
+            let mut target = self.rng.gen::<f64>() * remaining_weight;
+            let index = remaining
+                .iter()
+                .position(|&weight| {
+                    if target < weight {
+                        true
+                    } else {
+                        target -= weight;
+                        false
+                    }
+                })
+                // Only reached through rounding errors.
+                .or_else(|| remaining.iter().rposition(|&weight| weight > 0.))
+                .unwrap(); // Never fails because size is at most the total weight
+            let taken = remaining[index].min(1.);
+            remaining[index] -= taken;
+            remaining_weight -= taken;
+            counts[index] += 1;
+        }
+
+        counts.into_iter().filter(|&count| count > 0).collect()
+    }
+}
+
+impl<R> Reseed for WeightedBootstrap<R>
+where
+    R: SeedableRng,
+{
+    fn reseed(&mut self, seed: u64) {
+        self.rng = R::seed_from_u64(seed);
+    }
+}
+
+impl<R> SamplingMethod for WeightedBootstrap<R>
+where
+    R: Rng,
+{
+    type Count = f64;
+    type DegreeError = HighDegree;
+    type NumGroupsError = NumGroupsError;
+    type UnnormDistrError = UnnormDistrError;
+
+    fn degree(&self) -> usize {
+        self.degree
+    }
+
+    fn set_degree(&mut self, degree: usize) -> Result<&mut Self, Self::DegreeError> {
+        if self.num_groups > degree {
+            self.degree = degree;
+            Ok(self)
+        } else {
+            Err(HighDegree)
+        }
+    }
+
+    fn num_groups(&self) -> usize {
+        self.num_groups
+    }
+
+    fn set_num_groups(&mut self, num_groups: usize) -> Result<&mut Self, Self::NumGroupsError> {
+        if num_groups <= self.degree {
+            Err(LowNumGroups)?
+        }
+        if !enough_weight(&self.unnorm_distr, num_groups) {
+            Err(TooFewSamples)?
+        }
+        check_samples_rep(num_groups)?;
+        self.num_groups = num_groups;
+        Ok(self)
+    }
+
+    /// Change the unnormalized distribution.
+    ///
+    /// # Errors
+    ///
+    /// If there is a negative or non finite count,
+    /// or if the total weight is too low: it must be at least `2^{num_groups}`.
+    fn set_unnorm_distr(
+        &mut self,
+        unnorm_distr: &[f64],
+    ) -> Result<&mut Self, Self::UnnormDistrError> {
+        check_counts(unnorm_distr)?;
+        if !enough_weight(unnorm_distr, self.num_groups()) {
+            Err(TooFewSamples)?
+        }
+        self.unnorm_distr = unnorm_distr.to_vec();
+        Ok(self)
+    }
+
+    fn size_subsamples(&self) -> Vec<usize> {
+        let available_samples = total_weight(&self.unnorm_distr) as usize;
+        (0..self.num_groups())
+            .map(|i| available_samples >> i) // guaranteed to be at least 1
+            .collect()
+    }
+
+    fn samples_rep(&self) -> Vec<usize> {
+        (0..self.num_groups())
+            .map(|i| 4_usize.saturating_pow(i as u32)) // never saturates, see `check_samples_rep`
+            .collect()
+    }
+
+    fn pooled_unnorm_distr(&self) -> Vec<f64> {
+        self.unnorm_distr.clone()
+    }
+
+    fn naive_entropies(&mut self) -> Vec<(usize, f64)> {
+        let mut naive_entropies = Vec::with_capacity(self.total_samples());
+
+        let samples_rep = self.samples_rep();
+        for (group_index, group_size) in self.size_subsamples().iter().enumerate() {
+            for _ in 0..samples_rep[group_index] {
+                let unnorm_distr = self.draw_unnorm_distr(*group_size);
+                let naive_entropy_value = NaiveEstimator::new_unchecked(&unnorm_distr).entropy();
+                // Never fails because group_size is never null.
+                naive_entropies.push((*group_size, naive_entropy_value));
+            }
+        }
+        naive_entropies
+    }
+}
+
+fn total_weight(unnorm_distr: &[f64]) -> f64 {
+    unnorm_distr.iter().sum()
+}
+
+/// Returns whether the total weight is at least `2^{num_groups}`.
+fn enough_weight(unnorm_distr: &[f64], num_groups: usize) -> bool {
+    total_weight(unnorm_distr) >= 2_f64.powf(num_groups as f64)
+}
+
+fn check_counts(unnorm_distr: &[f64]) -> Result<(), InvalidCount> {
+    if unnorm_distr
+        .iter()
+        .all(|count| count.is_finite() && *count >= 0.)
+    {
+        Ok(())
+    } else {
+        Err(InvalidCount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+    use test_case::test_case;
+
+    use crate::{test::rng, Bootstrap, Estimator};
+
+    #[test]
+    fn new() {
+        let num_groups = 3;
+        let degree = 2;
+        let rng = rand::thread_rng();
+        WeightedBootstrap::new(&[0.5, 2.5, 3., 4., 5., 6.], num_groups, degree, rng).unwrap();
+    }
+
+    #[test_case(&[1., -2., 30.]; "negative")]
+    #[test_case(&[1., f64::NAN, 30.]; "nan")]
+    #[test_case(&[1., 2., 3.]; "too_few_samples")]
+    fn new_invalid(unnorm_distr: &[f64]) {
+        let rng = rand::thread_rng();
+        assert!(WeightedBootstrap::new(unnorm_distr, 3, 2, rng).is_err());
+    }
+
+    #[test]
+    fn size_subsamples() {
+        let num_groups = 3;
+        let degree = 2;
+        let rng = rand::thread_rng();
+        let weighted =
+            WeightedBootstrap::new(&[0.5, 2.5, 3., 4., 5., 6.5], num_groups, degree, rng).unwrap();
+
+        assert_eq!(vec![21, 10, 5], weighted.size_subsamples());
+    }
+
+    #[test]
+    fn dataset_size() {
+        let weighted = WeightedBootstrap::new(&[0.5, 2.5, 3., 4., 5., 6.5], 3, 2, rng(1)).unwrap();
+
+        assert_eq!(weighted.dataset_size(), 21);
+    }
+
+    #[test]
+    fn draw_unnorm_distr() {
+        let unnorm_distr = [0.5, 2.5, 3., 4.];
+        let mut weighted = WeightedBootstrap::new(&unnorm_distr, 2, 1, rng(1)).unwrap();
+
+        for _ in 0..100 {
+            let drawn = weighted.draw_unnorm_distr(10);
+            assert_eq!(drawn.iter().sum::<usize>(), 10);
+            assert!(drawn.len() <= unnorm_distr.len());
+        }
+    }
+
+    /// Integer and float paths agree on whole counts
+    #[test_case(&[1, 2, 3, 4, 5, 6]; "increasing")]
+    #[test_case(&[10, 10, 10, 10]; "uniform")]
+    #[test_case(&[40]; "one_element")]
+    fn whole_counts(unnorm_distr: &[usize]) {
+        let float_unnorm_distr: Vec<f64> = unnorm_distr.iter().map(|x| *x as f64).collect();
+        let reps = 200;
+        let bootstrap = Bootstrap::new(unnorm_distr, 3, 1, rng(1)).unwrap();
+        let weighted = WeightedBootstrap::new(&float_unnorm_distr, 3, 1, rng(2)).unwrap();
+
+        assert_eq!(bootstrap.size_subsamples(), weighted.size_subsamples());
+        assert_eq!(bootstrap.samples_rep(), weighted.samples_rep());
+
+        let mean = |replicates: Vec<f64>| replicates.iter().sum::<f64>() / reps as f64;
+        let expected = mean(Estimator::new(bootstrap).entropy_replicates(reps).unwrap());
+        let value = mean(Estimator::new(weighted).entropy_replicates(reps).unwrap());
+        assert_float_eq!(value, expected, abs <= 0.05);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test_case(r#"{"num_groups":3,"degree":2,"unnorm_distr":[1.5,2.5,3.5,4.5]}"#, true; "valid")]
+    #[test_case(r#"{"num_groups":3,"degree":3,"unnorm_distr":[1.5,2.5,3.5,4.5]}"#, false; "low_num_groups")]
+    #[test_case(r#"{"num_groups":3,"degree":2,"unnorm_distr":[1.5,2.5]}"#, false; "too_few_samples")]
+    #[test_case(r#"{"num_groups":3,"degree":2,"unnorm_distr":[-1.5,20.5]}"#, false; "invalid_count")]
+    fn serde_validated(json: &str, valid: bool) {
+        let deserialized: Result<WeightedBootstrap<rand::rngs::ThreadRng>, _> =
+            serde_json::from_str(json);
+        assert_eq!(deserialized.is_ok(), valid);
+    }
+}
@@ -0,0 +1,79 @@
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use crate::{utils::count_dup, NaiveEstimator};
+
+/// Returns the per-symbol block entropy of `sequence`, for all block sizes
+/// from `1` to `max_block`, as pairs `(block_size, value)`.
+///
+/// For each block size `n`, all overlapping blocks (n-grams) of `sequence` are
+/// counted and `value` is their naive entropy divided by `n`.
+/// For an ergodic source, this converges to the entropy rate as `n` grows.
+///
+/// # Remarks
+///
+/// If `max_block` exceeds the length of `sequence`, block sizes are
+/// truncated to the length of `sequence`.
+/// In particular, an empty sequence gives an empty output.
+///
+/// # Examples
+///
+/// A periodic sequence has a vanishing entropy rate.
+/// ```
+/// # use approx_entropy::block_entropy_scaling;
+/// let sequence = [0, 1, 0, 1, 0, 1, 0, 1];
+/// let scaling = block_entropy_scaling(&sequence, 3);
+/// assert_eq!(scaling.len(), 3);
+/// assert!(scaling[2].1 < scaling[0].1);
+/// ```
+pub fn block_entropy_scaling<T>(sequence: &[T], max_block: usize) -> Vec<(usize, f64)>
+where
+    T: Hash + Eq,
+{
+    let max_block = max_block.min(sequence.len());
+    (1..=max_block)
+        .map(|block_size| {
+            let blocks: Vec<&[T]> = sequence.windows(block_size).collect();
+            let unnorm_distr = count_dup(&blocks);
+            // There is at least one block, so the distribution is not null.
+            let block_entropy = NaiveEstimator::new_unchecked(&unnorm_distr).entropy();
+            (block_size, block_entropy / block_size as f64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+    use test_case::test_case;
+
+    #[test_case(&[], 3, 0; "empty")]
+    #[test_case(&[0, 1, 2], 5, 3; "max_block_too_big")]
+    #[test_case(&[0, 1, 2, 3, 4], 2, 2; "max_block_small")]
+    fn len(sequence: &[usize], max_block: usize, expected: usize) {
+        assert_eq!(block_entropy_scaling(sequence, max_block).len(), expected);
+    }
+
+    #[test]
+    fn periodic() {
+        let sequence: Vec<usize> = (0..100).map(|i| i % 2).collect();
+        let scaling = block_entropy_scaling(&sequence, 10);
+
+        assert_float_eq!(scaling[0].1, 2.0_f64.ln(), abs <= 1e-6);
+        for window in scaling.windows(2) {
+            assert!(window[1].1 < window[0].1);
+        }
+        assert!(scaling[9].1 < 0.1);
+    }
+
+    #[test]
+    fn not_clone() {
+        #[derive(Hash, PartialEq, Eq)]
+        struct Symbol(usize);
+
+        let sequence: Vec<Symbol> = (0..100).map(|i| Symbol(i % 2)).collect();
+        let expected = block_entropy_scaling(&(0..100).map(|i| i % 2).collect::<Vec<_>>(), 3);
+        assert_eq!(block_entropy_scaling(&sequence, 3), expected);
+    }
+}
@@ -0,0 +1,111 @@
+use thiserror::Error;
+
+const PRIOR_TOLERANCE: f64 = 1e-9;
+
+#[derive(Error, Debug)]
+pub enum ShrinkageError {
+    #[error("Invalid prior: its length differs from the length of the unnormalized distribution.")]
+    PriorLength,
+    #[error("Invalid prior: it must be non-negative and sum to one.")]
+    PriorNotNormalized,
+    #[error("Invalid strength: it must be finite and non-negative.")]
+    InvalidStrength,
+    #[error("Invalid unnormalized distribution: there must be at least one sample (or a positive strength).")]
+    NullDistribution,
+}
+
+/// Entropy of the empirical distribution shrunk towards `prior`.
+///
+/// The prior is added as `strength` pseudo-observations,
+/// so that the probability of the `i`-th element is
+/// `(n_i + strength * prior_i) / (N + strength)`,
+/// and the entropy of this distribution is returned.
+/// This is the shrinkage estimator of Hausser and Strimmer[^1],
+/// with a fixed shrinkage intensity.
+///
+/// With `strength = 0`, this is the naive entropy,
+/// while a large `strength` gives the entropy of `prior`.
+///
+/// # Errors
+///
+/// If `prior` does not have the same length as `unnorm_distr`,
+/// if it is not a probability distribution,
+/// if `strength` is negative or not finite,
+/// or if there are no samples and `strength` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::shrinkage_entropy;
+/// let unnorm_distr = [5, 1, 0, 0];
+/// let prior = [0.25; 4];
+/// println!("Entropy estimation: {:?}", shrinkage_entropy(&unnorm_distr, &prior, 2.));
+/// ```
+///
+/// [^1]: https://www.jmlr.org/papers/v10/hausser09a.html
+pub fn shrinkage_entropy(
+    unnorm_distr: &[usize],
+    prior: &[f64],
+    strength: f64,
+) -> Result<f64, ShrinkageError> {
+    if prior.len() != unnorm_distr.len() {
+        return Err(ShrinkageError::PriorLength);
+    }
+    if prior.iter().any(|&p| p.is_nan() || p < 0.)
+        || (prior.iter().sum::<f64>() - 1.).abs() > PRIOR_TOLERANCE
+    {
+        return Err(ShrinkageError::PriorNotNormalized);
+    }
+    if !strength.is_finite() || strength < 0. {
+        return Err(ShrinkageError::InvalidStrength);
+    }
+    let all = unnorm_distr.iter().map(|&count| count as f64).sum::<f64>() + strength;
+    if all <= 0. {
+        return Err(ShrinkageError::NullDistribution);
+    }
+
+    let entropy = unnorm_distr
+        .iter()
+        .zip(prior)
+        .map(|(&count, &p)| (count as f64 + strength * p) / all)
+        .filter(|&probability| probability > 0.)
+        .map(|probability| -probability * probability.ln())
+        .sum();
+    Ok(entropy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+    use test_case::test_case;
+
+    use crate::NaiveEstimator;
+
+    #[test]
+    fn no_strength() {
+        let unnorm_distr = [1, 2, 3, 4, 5, 6];
+        let prior = [1. / 6.; 6];
+        let expected = NaiveEstimator::new(&unnorm_distr).unwrap().entropy();
+        let value = shrinkage_entropy(&unnorm_distr, &prior, 0.).unwrap();
+        assert_float_eq!(value, expected, abs <= 1e-12);
+    }
+
+    #[test]
+    fn big_strength() {
+        let unnorm_distr = [10, 0, 0, 1];
+        let prior: [f64; 4] = [0.1, 0.2, 0.3, 0.4];
+        let expected: f64 = prior.iter().map(|p| -p * p.ln()).sum();
+        let value = shrinkage_entropy(&unnorm_distr, &prior, 1e9).unwrap();
+        assert_float_eq!(value, expected, abs <= 1e-6);
+    }
+
+    #[test_case(&[1, 2], &[1.], 1.; "prior_length")]
+    #[test_case(&[1, 2], &[0.5, 0.6], 1.; "prior_sum")]
+    #[test_case(&[1, 2], &[1.5, -0.5], 1.; "prior_negative")]
+    #[test_case(&[1, 2], &[0.5, 0.5], -1.; "negative_strength")]
+    #[test_case(&[0, 0], &[0.5, 0.5], 0.; "null")]
+    fn invalid(unnorm_distr: &[usize], prior: &[f64], strength: f64) {
+        assert!(shrinkage_entropy(unnorm_distr, prior, strength).is_err());
+    }
+}
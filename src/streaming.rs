@@ -0,0 +1,116 @@
+use core::hash::Hash;
+use std::collections::HashMap;
+
+use crate::{estimator::FittingError, Estimator};
+
+/// Entropy estimation from samples arriving incrementally.
+///
+/// Only the counts of the samples seen so far are kept,
+/// and each call to [`entropy`] runs an [`Estimator`] over them.
+///
+/// # Remarks
+///
+/// Counts are sorted before being handed to the [`Estimator`],
+/// so that the estimation depends only on the counts and the seed,
+/// and not on the order in which samples arrived.
+///
+/// Default values are given to tunable parameters, as in `Estimator::from_seed`.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::StreamingEstimator;
+/// let mut estimator = StreamingEstimator::new(1);
+/// estimator.push_batch(&[1, 2, 2, 3, 3, 3]);
+/// estimator.push(4);
+/// estimator.push(4);
+/// println!("Entropy estimation: {:?}", estimator.entropy());
+/// ```
+///
+/// [`entropy`]: #method.entropy
+/// [`Estimator`]: struct.Estimator.html
+#[derive(Debug, Clone)]
+pub struct StreamingEstimator<T> {
+    counts: HashMap<T, usize>,
+    seed: u64,
+}
+
+impl<T> StreamingEstimator<T>
+where
+    T: Hash + Eq + Clone,
+{
+    /// Constructs a new `StreamingEstimator`, without samples,
+    /// whose estimations use random number generators seeded with `seed`.
+    pub fn new(seed: u64) -> Self {
+        StreamingEstimator {
+            counts: HashMap::new(),
+            seed,
+        }
+    }
+
+    /// Adds one sample.
+    pub fn push(&mut self, sample: T) {
+        *self.counts.entry(sample).or_insert(0) += 1;
+    }
+
+    /// Adds all samples in `samples`.
+    pub fn push_batch(&mut self, samples: &[T]) {
+        for sample in samples {
+            self.push(sample.clone());
+        }
+    }
+
+    /// Estimates the entropy of the samples seen so far.
+    ///
+    /// # Errors
+    ///
+    /// If there are numerical instabilities.
+    ///
+    /// # Panics
+    ///
+    /// If there are too few samples for the default number of groups.
+    pub fn entropy(&mut self) -> Result<f64, FittingError> {
+        let mut unnorm_distr: Vec<usize> = self.counts.values().copied().collect();
+        unnorm_distr.sort_unstable();
+        Estimator::from_seed(&unnorm_distr, self.seed).entropy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    use crate::count_dup;
+
+    #[test]
+    fn two_batches() {
+        let first: Vec<usize> = (0..50).map(|i| i % 7).collect();
+        let second: Vec<usize> = (0..30).map(|i| i % 11).collect();
+        let mut estimator = StreamingEstimator::new(1);
+        estimator.push_batch(&first);
+        estimator.push_batch(&second);
+
+        let concatenated = [first, second].concat();
+        let mut unnorm_distr = count_dup(&concatenated);
+        unnorm_distr.sort_unstable();
+        let expected = Estimator::from_seed(&unnorm_distr, 1).entropy().unwrap();
+
+        assert_float_eq!(estimator.entropy().unwrap(), expected, abs <= 1e-12);
+    }
+
+    #[test]
+    fn order_independent() {
+        let samples: Vec<char> = "abracadabra arbadacarba".chars().collect();
+        let mut forward = StreamingEstimator::new(3);
+        let mut backward = StreamingEstimator::new(3);
+        samples.iter().for_each(|&c| forward.push(c));
+        samples.iter().rev().for_each(|&c| backward.push(c));
+
+        assert_float_eq!(
+            forward.entropy().unwrap(),
+            backward.entropy().unwrap(),
+            abs <= 1e-12
+        );
+    }
+}
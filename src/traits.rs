@@ -1,6 +1,39 @@
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "std")]
 use std::error::Error;
 
+/// Without `std`, errors only need to be printable.
+#[cfg(not(feature = "std"))]
+pub trait Error: core::fmt::Debug + core::fmt::Display {}
+
+#[cfg(not(feature = "std"))]
+impl<T> Error for T where T: core::fmt::Debug + core::fmt::Display {}
+
+/// Numeric type usable as the number of occurrences of an element
+/// in an unnormalized distribution.
+///
+/// Integer counts are the default throughout the crate,
+/// but fractional counts (e.g. weighted samples) are also supported.
+pub trait Count: Copy {
+    /// Returns the count as a floating point number.
+    fn to_f64(self) -> f64;
+}
+
+impl Count for usize {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Count for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
 pub trait SamplingMethod {
+    /// Type of the entries of the unnormalized distribution.
+    type Count: Count;
     type DegreeError: Error;
     type NumGroupsError: Error;
     type UnnormDistrError: Error;
@@ -15,7 +48,7 @@ pub trait SamplingMethod {
     /// Change the unnormalized distribution from which subsamples will be taken.
     fn set_unnorm_distr(
         &mut self,
-        unnorm_distr: &[usize],
+        unnorm_distr: &[Self::Count],
     ) -> Result<&mut Self, Self::UnnormDistrError>;
 
     /// Size of the subsamples, for each group.
@@ -38,19 +71,87 @@ pub trait SamplingMethod {
     /// entry of the output of `size_subsamples`.
     fn samples_rep(&self) -> Vec<usize>;
 
+    /// Returns whether there are more distinct subsample sizes than the degree,
+    /// so that a polynomial of that degree can be fitted to the naive entropy estimations.
+    ///
+    /// This is checked before drawing any subsample.
+    fn can_fit(&self) -> bool {
+        num_distinct_sizes(self) > self.degree()
+    }
+
     /// Total number of naive entropy estimation used to fit a polynomial.
     ///
     /// This is equivalent to `self.samples_rep().iter().sum()`.
     ///
     /// # Remarks
     ///
-    /// This might not correspond to the total number of samples of the underlying distribution.
+    /// Despite its name, this is **not** the number of samples of the underlying distribution,
+    /// which is given by [`dataset_size`].
+    ///
+    /// [`dataset_size`]: #method.dataset_size
     fn total_samples(&self) -> usize {
         self.samples_rep().iter().sum()
     }
 
+    /// Total number of samples of the underlying distribution.
+    ///
+    /// By default, this is the sum of the pooled unnormalized distribution,
+    /// rounded down for fractional counts.
+    ///
+    /// # Remarks
+    ///
+    /// Not to be confused with [`total_samples`], the number of naive entropy estimations.
+    ///
+    /// [`total_samples`]: #method.total_samples
+    fn dataset_size(&self) -> usize {
+        self.pooled_unnorm_distr()
+            .into_iter()
+            .map(Count::to_f64)
+            .sum::<f64>() as usize
+    }
+
+    /// Returns the unnormalized distribution of all available samples,
+    /// pooled together.
+    fn pooled_unnorm_distr(&self) -> Vec<Self::Count>;
+
     /// Returns all naive entropy estimations used for fitting a polynomial,
     /// as pairs `(size, value)`, where `size` is the size of the subsample used
     /// and `value` the corresponding naive entropy value.
     fn naive_entropies(&mut self) -> Vec<(usize, f64)>;
+
+    /// Returns the same naive entropy estimations as [`naive_entropies`], one at a time,
+    /// so that they can be accumulated without an intermediate vector.
+    ///
+    /// By default, [`naive_entropies`] is collected first.
+    /// Sampling methods can override it to compute estimations lazily,
+    /// as `Bootstrap` does.
+    ///
+    /// [`naive_entropies`]: #tymethod.naive_entropies
+    fn naive_entropies_iter(&mut self) -> Box<dyn Iterator<Item = (usize, f64)> + '_> {
+        Box::new(self.naive_entropies().into_iter())
+    }
+}
+
+/// Number of distinct sizes in [`SamplingMethod::size_subsamples`].
+///
+/// [`SamplingMethod::size_subsamples`]: trait.SamplingMethod.html#tymethod.size_subsamples
+pub(crate) fn num_distinct_sizes<M>(sampling_method: &M) -> usize
+where
+    M: SamplingMethod + ?Sized,
+{
+    let mut distinct_sizes = sampling_method.size_subsamples();
+    distinct_sizes.sort_unstable();
+    distinct_sizes.dedup();
+    distinct_sizes.len()
+}
+
+/// Sampling methods driven by a random number generator that can be reseeded.
+///
+/// Sampling methods without randomness (like [`FixedPartition`])
+/// do not implement this trait.
+///
+/// [`FixedPartition`]: struct.FixedPartition.html
+pub trait Reseed {
+    /// Replaces the state of the random number generator by one seeded with `seed`.
+    fn reseed(&mut self, seed: u64);
 }
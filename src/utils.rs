@@ -1,4 +1,7 @@
-use std::{collections::HashMap, hash::Hash};
+use alloc::vec::Vec;
+use core::hash::Hash;
+use hashbrown::HashMap;
+use num_traits::Float;
 
 /// Returns a vector containing the number of repetitions
 /// of each distinct element in `samples`.
@@ -8,6 +11,7 @@ use std::{collections::HashMap, hash::Hash};
 /// There is no guarantee on the order of the output.
 /// In particular, the correspondance between the original element
 /// and its number of occurrances is lost.
+/// Use [`count_dup_map`] to keep it.
 ///
 /// # Examples
 ///
@@ -17,28 +21,178 @@ use std::{collections::HashMap, hash::Hash};
 /// let samples = ['a', 'b', 'c', 'c', 'a', 'b'];
 /// assert_eq!(count_dup(&samples), vec![2, 2, 2]);
 /// ```
+/// [`count_dup_map`]: fn.count_dup_map.html
 pub fn count_dup<T>(samples: &[T]) -> Vec<usize>
 where
     T: Hash + Eq + Clone,
+{
+    count_dup_iter(samples.iter().cloned())
+}
+
+/// Same as [`count_dup`], but consuming `samples` one at a time,
+/// so that they never need to be all in memory.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::count_dup_iter;
+/// let unnorm_distr = count_dup_iter((0..1_000_000).map(|i| i % 4));
+/// assert_eq!(unnorm_distr, vec![250_000; 4]);
+/// ```
+///
+/// [`count_dup`]: fn.count_dup.html
+pub fn count_dup_iter<T, I>(samples: I) -> Vec<usize>
+where
+    T: Hash + Eq,
+    I: IntoIterator<Item = T>,
+{
+    count_iter(samples).into_values().collect()
+}
+
+/// Returns a map from each distinct element in `samples`
+/// to its number of repetitions.
+///
+/// The map is a `hashbrown::HashMap`, with or without the `std` feature.
+///
+/// # Examples
+///
+/// The mode of the samples.
+/// ```
+/// # use approx_entropy::count_dup_map;
+/// let samples = ['a', 'b', 'c', 'c', 'a', 'c'];
+/// let distribution = count_dup_map(&samples);
+/// let mode = distribution.iter().max_by_key(|(_, &count)| count).unwrap().0;
+/// assert_eq!(*mode, 'c');
+/// ```
+pub fn count_dup_map<T>(samples: &[T]) -> HashMap<T, usize>
+where
+    T: Hash + Eq + Clone,
+{
+    count_iter(samples.iter().cloned())
+}
+
+/// Same as [`count_dup`], but counts are in order of first appearance in `samples`,
+/// so that the result does not depend on the hasher.
+///
+/// [`count_dup`]: fn.count_dup.html
+#[cfg(feature = "std")]
+pub(crate) fn count_dup_ordered<T>(samples: &[T]) -> Vec<usize>
+where
+    T: Hash + Eq,
+{
+    let mut indices = HashMap::<&T, usize>::new();
+    let mut unnorm_distr = Vec::new();
+    for sample in samples {
+        let index = *indices.entry(sample).or_insert_with(|| {
+            unnorm_distr.push(0);
+            unnorm_distr.len() - 1
+        });
+        unnorm_distr[index] += 1;
+    }
+    unnorm_distr
+}
+
+fn count_iter<T, I>(samples: I) -> HashMap<T, usize>
+where
+    T: Hash + Eq,
+    I: IntoIterator<Item = T>,
 {
     let mut distribution = HashMap::<T, usize>::new();
     for i in samples {
-        let count = distribution.entry(i.clone()).or_insert(0);
+        let count = distribution.entry(i).or_insert(0);
         *count += 1
     }
+    distribution
+}
 
-    let mut vec = Vec::<usize>::new();
+/// Total number of samples of an unnormalized distribution,
+/// or `None` if it does not fit in a `usize`.
+pub(crate) fn checked_total(unnorm_distr: &[usize]) -> Option<usize> {
+    unnorm_distr
+        .iter()
+        .try_fold(0_usize, |total, &count| total.checked_add(count))
+}
 
-    for (_, occurrence) in &distribution {
-        vec.push(*occurrence)
+/// Digamma function, the logarithmic derivative of the gamma function.
+///
+/// Uses the recurrence `ψ(x) = ψ(x + 1) - 1/x` to shift the argument
+/// and then the asymptotic expansion, which is accurate for `x >= 6`.
+pub(crate) fn digamma(mut x: f64) -> f64 {
+    let mut result = 0.;
+    while x < 6. {
+        result -= 1. / x;
+        x += 1.;
     }
+    let inv = 1. / x;
+    let inv2 = inv * inv;
+    let series = inv2 * (1. / 12. - inv2 * (1. / 120. - inv2 * (1. / 252. - inv2 / 240.)));
+    result + Float::ln(x) - 0.5 * inv - series
+}
 
-    vec
+/// Quantile function of the standard normal distribution.
+///
+/// Uses the rational approximation of Acklam,
+/// with a relative error smaller than `1.15e-9`.
+///
+/// # Panics
+///
+/// If `p` is not strictly between `0` and `1`.
+#[cfg(feature = "std")]
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    assert!(p > 0. && p < 1., "probability must be in (0, 1)");
+
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    };
+    if p < P_LOW {
+        tail((-2. * p.ln()).sqrt())
+    } else if p > 1. - P_LOW {
+        -tail((-2. * (1. - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use float_eq::assert_float_eq;
+    use test_case::test_case;
 
     #[test]
     fn compute_count_dup() {
@@ -47,4 +201,54 @@ mod tests {
         output.sort();
         assert_eq!(output, vec![1, 2, 3]);
     }
+
+    #[test]
+    fn compute_count_dup_iter() {
+        let mut output = count_dup_iter((0..100).map(|i| i % 7));
+        output.sort();
+        assert_eq!(output, vec![14, 14, 14, 14, 14, 15, 15]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn compute_count_dup_ordered() {
+        let samples = ["b", "c", "a", "c", "c", "b"];
+        assert_eq!(count_dup_ordered(&samples), vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn compute_count_dup_map() {
+        let samples = ["a", "b", "b", "c", "c", "c", "d"];
+        let distribution: hashbrown::HashMap<&str, usize> = count_dup_map(&samples);
+        assert_eq!(distribution["c"], 3);
+
+        let mut values: Vec<usize> = distribution.into_values().collect();
+        values.sort();
+        let mut output = count_dup(&samples);
+        output.sort();
+        assert_eq!(values, output);
+    }
+
+    #[cfg(feature = "std")]
+    #[test_case(&[], Some(0); "empty")]
+    #[test_case(&[1, 2, 3], Some(6); "small")]
+    #[test_case(&[usize::MAX, 0], Some(usize::MAX); "max")]
+    #[test_case(&[usize::MAX, 1], None; "overflow")]
+    fn compute_checked_total(unnorm_distr: &[usize], expected: Option<usize>) {
+        assert_eq!(checked_total(unnorm_distr), expected);
+    }
+
+    #[test_case(1., -0.5772156649; "one")]
+    #[test_case(0.5, -1.9635100260; "half")]
+    #[test_case(10., 2.2517525891; "ten")]
+    fn compute_digamma(x: f64, expected: f64) {
+        assert_float_eq!(digamma(x), expected, abs <= 1e-8);
+    }
+
+    #[test_case(0.5, 0.; "median")]
+    #[test_case(0.975, 1.959963985; "upper")]
+    #[test_case(0.01, -2.326347874; "lower_tail")]
+    fn compute_normal_quantile(p: f64, expected: f64) {
+        assert_float_eq!(normal_quantile(p), expected, abs <= 1e-8);
+    }
 }
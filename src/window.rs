@@ -0,0 +1,159 @@
+use core::hash::Hash;
+use std::collections::{HashMap, VecDeque};
+
+use crate::{estimator::FittingError, Estimator};
+
+/// Extension trait for estimating entropy over a sliding window of an iterator.
+///
+/// # Examples
+///
+/// ```
+/// # use approx_entropy::EntropyWindowExt;
+/// let stream = (0..100).map(|i| i % 5);
+/// for estimation in stream.entropy_windows(20, 1) {
+///     println!("Entropy estimation: {:?}", estimation);
+/// }
+/// ```
+pub trait EntropyWindowExt<T>: Iterator<Item = T> + Sized
+where
+    T: Hash + Eq + Clone,
+{
+    /// Returns an iterator over entropy estimations of the last `window` samples,
+    /// one for each position of the window.
+    ///
+    /// Counts are updated as samples enter and leave the window,
+    /// and sorted before being handed to an [`Estimator`] seeded with `seed`,
+    /// as in `Estimator::from_seed`.
+    ///
+    /// # Remarks
+    ///
+    /// If there are fewer than `window` samples, nothing is yielded.
+    ///
+    /// # Panics
+    ///
+    /// When iterating, if `window` is too small for the default number of groups.
+    ///
+    /// [`Estimator`]: struct.Estimator.html
+    fn entropy_windows(self, window: usize, seed: u64) -> EntropyWindows<Self, T> {
+        EntropyWindows {
+            iter: self,
+            window,
+            seed,
+            samples: VecDeque::with_capacity(window),
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<I, T> EntropyWindowExt<T> for I
+where
+    I: Iterator<Item = T>,
+    T: Hash + Eq + Clone,
+{
+}
+
+/// Iterator over entropy estimations of a sliding window.
+///
+/// This struct is created by [`entropy_windows`].
+///
+/// [`entropy_windows`]: trait.EntropyWindowExt.html#method.entropy_windows
+#[derive(Debug, Clone)]
+pub struct EntropyWindows<I, T> {
+    iter: I,
+    window: usize,
+    seed: u64,
+    samples: VecDeque<T>,
+    counts: HashMap<T, usize>,
+}
+
+impl<I, T> EntropyWindows<I, T>
+where
+    T: Hash + Eq + Clone,
+{
+    fn remove_oldest(&mut self) {
+        if let Some(sample) = self.samples.pop_front() {
+            let count = self.counts.get_mut(&sample).unwrap(); // Never fails, it is in the window
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&sample);
+            }
+        }
+    }
+
+    fn add(&mut self, sample: T) {
+        *self.counts.entry(sample.clone()).or_insert(0) += 1;
+        self.samples.push_back(sample);
+    }
+}
+
+impl<I, T> Iterator for EntropyWindows<I, T>
+where
+    I: Iterator<Item = T>,
+    T: Hash + Eq + Clone,
+{
+    type Item = Result<f64, FittingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.samples.len() == self.window {
+            self.remove_oldest();
+        }
+        while self.samples.len() < self.window {
+            let sample = self.iter.next()?;
+            self.add(sample);
+        }
+
+        let mut unnorm_distr: Vec<usize> = self.counts.values().copied().collect();
+        unnorm_distr.sort_unstable();
+        Some(Estimator::from_seed(&unnorm_distr, self.seed).entropy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn num_windows() {
+        let windows = (0..100).map(|i| i % 3).entropy_windows(40, 1);
+        assert_eq!(windows.count(), 61);
+    }
+
+    #[test]
+    fn too_short() {
+        let windows = (0..10).entropy_windows(40, 1);
+        assert_eq!(windows.count(), 0);
+    }
+
+    #[test]
+    fn periodic() {
+        let estimations: Vec<f64> = (0..200)
+            .map(|i| i % 4)
+            .entropy_windows(64, 1)
+            .map(Result::unwrap)
+            .collect();
+
+        for estimation in &estimations {
+            assert_float_eq!(*estimation, estimations[0], abs <= 1e-12);
+        }
+        assert_float_eq!(estimations[0], 4_f64.ln(), abs <= 0.1);
+    }
+
+    #[test]
+    fn matches_rebuilding() {
+        let stream: Vec<usize> = (0..60).map(|i| (i * i) % 7).collect();
+        let window = 30;
+        let estimations: Vec<f64> = stream
+            .iter()
+            .entropy_windows(window, 2)
+            .map(Result::unwrap)
+            .collect();
+
+        for (start, estimation) in estimations.iter().enumerate() {
+            let mut unnorm_distr = crate::count_dup(&stream[start..start + window]);
+            unnorm_distr.sort_unstable();
+            let expected = Estimator::from_seed(&unnorm_distr, 2).entropy().unwrap();
+            assert_float_eq!(*estimation, expected, abs <= 1e-12);
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Checks that the naive path is usable without `std`.
+//!
+//! Run with `cargo test --no-default-features --test no_std`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec;
+use approx_entropy::{count_dup, NaiveEstimator};
+
+#[test]
+fn naive_entropy() {
+    let samples = ['a', 'b', 'c', 'c', 'a', 'b'];
+    let unnorm_distr = count_dup(&samples);
+    assert_eq!(unnorm_distr, vec![2, 2, 2]);
+
+    let entropy = NaiveEstimator::new(&unnorm_distr).unwrap().entropy();
+    assert!((entropy - core::f64::consts::LN_2 - 0.4054651081081644).abs() < 1e-12);
+}
+
+#[test]
+fn null_distribution() {
+    assert!(NaiveEstimator::new(&[0_usize, 0]).is_err());
+}